@@ -0,0 +1,383 @@
+//! Generates Rust modules of validated identifiers from `build.rs`, for
+//! tables too large to go through `define_identifier!`.
+//!
+//! Entries are read from JSON, TOML or CSV, validated, and written out as a
+//! function per identifier plus `COUNT`, `ALL` and `all()`, the same shape
+//! `include_identifiers!` generates.
+//!
+//! # Examples
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! use namespacedkey_codegen::Codegen;
+//!
+//! let json = std::fs::read_to_string("blocks.json").unwrap();
+//! Codegen::new()
+//!     .marker("crate::Block")
+//!     .json(&json)
+//!     .unwrap()
+//!     .write_to_out_dir("blocks.rs")
+//!     .unwrap();
+//! ```
+//!
+//! Then in the crate:
+//!
+//! ```ignore
+//! pub mod blocks {
+//!     include!(concat!(env!("OUT_DIR"), "/blocks.rs"));
+//! }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    io,
+    path::{Path, PathBuf},
+};
+
+use namespacedkey_core::{IdentifierUntyped, ParseError};
+use serde::Deserialize;
+
+/// Error returned when the input can't be read or holds an invalid entry.
+#[derive(Debug, thiserror::Error)]
+pub enum CodegenError {
+    /// The JSON input is malformed.
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+
+    /// The TOML input is malformed.
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+
+    /// An entry is not a valid identifier.
+    #[error("entry {entry}: {error}")]
+    Parse {
+        /// The 1-based entry number within its input, or line number for
+        /// CSV.
+        entry: usize,
+        /// Why it is invalid.
+        error: ParseError,
+    },
+
+    /// An entry's name is not a valid Rust identifier.
+    #[error("entry {entry}: invalid name {name:?}")]
+    InvalidName {
+        /// The 1-based entry number within its input, or line number for
+        /// CSV.
+        entry: usize,
+        /// The name.
+        name: String,
+    },
+
+    /// Two entries have the same name or identifier.
+    #[error("entry {entry}: duplicate {what}, first in entry {first}")]
+    Duplicate {
+        /// The 1-based entry number within its input, or line number for
+        /// CSV.
+        entry: usize,
+        /// The entry it duplicates.
+        first: usize,
+        /// `name` or `identifier`.
+        what: &'static str,
+    },
+
+    /// `OUT_DIR` is not set, outside a build script.
+    #[error("OUT_DIR is not set")]
+    NoOutDir,
+
+    /// Writing the module failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+}
+
+/// One entry of a JSON or TOML list: an identifier, optionally named.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Item {
+    Id(String),
+    Named { name: String, id: String },
+}
+
+#[derive(Deserialize)]
+struct TomlInput {
+    identifiers: Vec<Item>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    id: IdentifierUntyped,
+}
+
+/// Builds a module of identifiers.
+#[derive(Debug)]
+pub struct Codegen {
+    marker: String,
+    entries: Vec<Entry>,
+    names: HashMap<String, usize>,
+    ids: HashMap<IdentifierUntyped, usize>,
+}
+
+impl Default for Codegen {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Codegen {
+    /// Creates an empty module with `()` as the marker type.
+    pub fn new() -> Self {
+        Self {
+            marker: "()".to_owned(),
+            entries: Vec::new(),
+            names: HashMap::new(),
+            ids: HashMap::new(),
+        }
+    }
+
+    /// Sets the marker type of the generated identifiers, as a path valid
+    /// where the module is included, like `crate::Block`.
+    pub fn marker(mut self, ty: &str) -> Self {
+        ty.clone_into(&mut self.marker);
+        self
+    }
+
+    /// Adds one identifier. Its function is `id_{name}`, with `name`
+    /// defaulting to the identifier with every character other than ASCII
+    /// letters, digits and `_` replaced by `_`. Errors report it as entry 1.
+    pub fn entry(
+        mut self,
+        name: Option<&str>,
+        id: &str,
+    ) -> Result<Self, CodegenError> {
+        self.push(1, name, id)?;
+        Ok(self)
+    }
+
+    /// Adds the identifiers from a JSON array whose elements are either
+    /// identifier strings or `{ "name": ..., "id": ... }` objects.
+    pub fn json(self, input: &str) -> Result<Self, CodegenError> {
+        let items: Vec<Item> = serde_json::from_str(input)?;
+        self.items(items)
+    }
+
+    /// Adds the identifiers from a TOML document with an `identifiers`
+    /// array, whose elements are like those for [`json`](Self::json).
+    pub fn toml(self, input: &str) -> Result<Self, CodegenError> {
+        let TomlInput { identifiers } = toml::from_str(input)?;
+        self.items(identifiers)
+    }
+
+    /// Adds the identifiers from CSV lines of `id` or `name,id`. As in
+    /// list files, everything from a `#` to the end of the line is a
+    /// comment and blank lines are skipped. Errors report the line number.
+    pub fn csv(mut self, input: &str) -> Result<Self, CodegenError> {
+        for (idx, line) in input.lines().enumerate() {
+            let line = match line.split_once('#') {
+                Some((content, _)) => content.trim(),
+                None => line.trim(),
+            };
+            if line.is_empty() {
+                continue;
+            }
+            match line.split_once(',') {
+                Some((name, id)) => {
+                    self.push(idx + 1, Some(name.trim()), id.trim())?
+                }
+                None => self.push(idx + 1, None, line)?,
+            }
+        }
+        Ok(self)
+    }
+
+    fn items(mut self, items: Vec<Item>) -> Result<Self, CodegenError> {
+        for (idx, item) in items.into_iter().enumerate() {
+            let entry = idx + 1;
+            match item {
+                Item::Id(id) => self.push(entry, None, &id)?,
+                Item::Named { name, id } => {
+                    self.push(entry, Some(&name), &id)?
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    fn push(
+        &mut self,
+        entry: usize,
+        name: Option<&str>,
+        id: &str,
+    ) -> Result<(), CodegenError> {
+        let id = IdentifierUntyped::parse(id)
+            .map_err(|error| CodegenError::Parse { entry, error })?;
+        let name = match name {
+            Some(name) if is_valid_name(name) => name.to_owned(),
+            Some(name) => {
+                return Err(CodegenError::InvalidName {
+                    entry,
+                    name: name.to_owned(),
+                });
+            }
+            None => id
+                .as_str()
+                .chars()
+                .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+                .collect(),
+        };
+        if let Some(&first) = self.ids.get(&id) {
+            return Err(CodegenError::Duplicate {
+                entry,
+                first,
+                what: "identifier",
+            });
+        }
+        if let Some(&first) = self.names.get(&name) {
+            return Err(CodegenError::Duplicate {
+                entry,
+                first,
+                what: "name",
+            });
+        }
+        self.names.insert(name.clone(), entry);
+        self.ids.insert(id.clone(), entry);
+        self.entries.push(Entry { name, id });
+        Ok(())
+    }
+
+    /// Returns the module's source code.
+    pub fn generate(&self) -> String {
+        let ty = &self.marker;
+        let count = self.entries.len();
+        let mut out = String::new();
+        // Writing to a `String` can't fail.
+        let _ = writeln!(
+            out,
+            "// @generated by namespacedkey_codegen. Do not edit.\n\n\
+             /// The number of identifiers in this module.\n\
+             pub const COUNT: usize = {count};\n\n\
+             /// Every identifier in this module, as strings.\n\
+             pub const ALL: [&str; COUNT] = ["
+        );
+        for Entry { id, .. } in &self.entries {
+            let _ = writeln!(out, "    {:?},", id.as_str());
+        }
+        let _ = writeln!(out, "];");
+        for Entry { name, id } in &self.entries {
+            let _ = writeln!(
+                out,
+                "\n/// `{id}`\n\
+                 pub fn id_{name}() -> namespacedkey::Identifier<{ty}> {{\n    \
+                     static ONCE: ::std::sync::OnceLock<namespacedkey::Identifier<{ty}>> =\n        \
+                         ::std::sync::OnceLock::new();\n    \
+                     ONCE.get_or_init(|| namespacedkey::Identifier::__from_validated_parts({:?}, {:?}))\n        \
+                         .clone()\n\
+                 }}",
+                id.namespace(),
+                id.value(),
+            );
+        }
+        let _ = writeln!(
+            out,
+            "\n/// Returns every identifier in this module.\n\
+             pub fn all() -> impl ::core::iter::Iterator<Item = namespacedkey::Identifier<{ty}>> {{\n    \
+                 let ids: [fn() -> namespacedkey::Identifier<{ty}>; COUNT] = ["
+        );
+        for Entry { name, .. } in &self.entries {
+            let _ = writeln!(out, "        id_{name},");
+        }
+        let _ = writeln!(out, "    ];\n    ids.into_iter().map(|id| id())\n}}");
+        out
+    }
+
+    /// Writes the module to `path`.
+    pub fn write_to(&self, path: impl AsRef<Path>) -> Result<(), CodegenError> {
+        std::fs::write(path, self.generate())?;
+        Ok(())
+    }
+
+    /// Writes the module to `file_name` in `OUT_DIR`, returning the full
+    /// path.
+    pub fn write_to_out_dir(
+        &self,
+        file_name: &str,
+    ) -> Result<PathBuf, CodegenError> {
+        let dir = std::env::var_os("OUT_DIR").ok_or(CodegenError::NoOutDir)?;
+        let path = Path::new(&dir).join(file_name);
+        self.write_to(&path)?;
+        Ok(path)
+    }
+}
+
+/// Whether `id_{name}` is a valid Rust identifier.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Codegen, CodegenError};
+
+    #[test]
+    fn formats_agree() {
+        let json = Codegen::new()
+            .json(
+                r#"["game:stone", {"name": "sword", "id": "game:item/sword"}]"#,
+            )
+            .unwrap();
+        let toml = Codegen::new()
+            .toml(
+                r#"identifiers = ["game:stone", { name = "sword", id = "game:item/sword" }]"#,
+            )
+            .unwrap();
+        let csv = Codegen::new()
+            .csv("# blocks\ngame:stone\n\nsword, game:item/sword\n")
+            .unwrap();
+        assert_eq!(json.generate(), toml.generate());
+        assert_eq!(json.generate(), csv.generate());
+    }
+
+    #[test]
+    fn generates_functions() {
+        let code = Codegen::new()
+            .marker("crate::Block")
+            .entry(None, "game:item/sword")
+            .unwrap()
+            .generate();
+        assert!(code.contains("pub const COUNT: usize = 1;"));
+        assert!(code.contains(
+            "pub fn id_game_item_sword() -> namespacedkey::Identifier<crate::Block>"
+        ));
+        assert!(
+            code.contains(r#"__from_validated_parts("game", "item/sword")"#)
+        );
+    }
+
+    #[test]
+    fn reports_bad_entries() {
+        let err = Codegen::new().csv("game:stone\ngame:Dirt\n").unwrap_err();
+        assert!(matches!(err, CodegenError::Parse { entry: 2, .. }));
+
+        let err = Codegen::new()
+            .json(r#"["game:stone", "game:dirt", "game:stone"]"#)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "entry 3: duplicate identifier, first in entry 1"
+        );
+
+        let err = Codegen::new()
+            .json(r#"["game:stone"]"#)
+            .unwrap()
+            .json(r#"["game:dirt", "game:Grass"]"#)
+            .unwrap_err();
+        assert!(matches!(err, CodegenError::Parse { entry: 2, .. }));
+
+        let err = Codegen::new().csv("a-b,game:stone").unwrap_err();
+        assert!(matches!(err, CodegenError::InvalidName { entry: 1, .. }));
+    }
+}
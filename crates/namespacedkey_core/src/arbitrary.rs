@@ -0,0 +1,73 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    Identifier,
+    generate::{Alphabet, capped},
+};
+
+/// Picks at least `min` chars from `chars`, as many as `u` asks for,
+/// stopping early rather than going past `max` bytes.
+fn arbitrary_chars(
+    u: &mut Unstructured<'_>,
+    chars: &[char],
+    min: usize,
+    max: Option<usize>,
+) -> Result<String> {
+    let len = u.arbitrary_len::<u8>()?.max(min);
+    let chars = (0..len)
+        .map(|_| u.choose(chars).copied())
+        .collect::<Result<Vec<_>>>()?;
+    Ok(capped(chars, max))
+}
+
+/// Each part is sized to fit the installed length limits, given the other.
+/// An empty namespace is allowed where the default namespace it falls back
+/// to fits, like [`Identifier::parse`] does. Fuzz input that still leads to
+/// a rejected candidate is reported as
+/// [`arbitrary::Error::IncorrectFormat`].
+impl<'a, T> Arbitrary<'a> for Identifier<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let alphabet = Alphabet::configured();
+        let namespace = arbitrary_chars(
+            u,
+            &alphabet.namespace,
+            alphabet.namespace_min(),
+            alphabet.namespace_max(),
+        )?;
+        let value = arbitrary_chars(
+            u,
+            &alphabet.value,
+            1,
+            alphabet.value_max(&namespace),
+        )?;
+        Identifier::new(namespace, value)
+            .map_err(|_| arbitrary::Error::IncorrectFormat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn arbitrary_identifiers_round_trip() {
+        let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let mut u = Unstructured::new(&data);
+        while let Ok(id) = IdentifierUntyped::arbitrary(&mut u) {
+            let parsed = IdentifierUntyped::parse(id.to_string()).unwrap();
+            assert_eq!(parsed, id);
+            if u.is_empty() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn arbitrary_from_empty_input() {
+        let mut u = Unstructured::new(&[]);
+        let id = IdentifierUntyped::arbitrary(&mut u).unwrap();
+        assert!(!id.value().is_empty());
+    }
+}
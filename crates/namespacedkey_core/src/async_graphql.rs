@@ -0,0 +1,64 @@
+//! async-graphql support. Identifiers are exposed as an `Identifier` scalar
+//! holding their `namespace:value` string.
+
+use async_graphql::{
+    InputValueError, InputValueResult, Scalar, ScalarType, Value,
+};
+
+use crate::Identifier;
+
+/// Malformed input is reported as an input validation error carrying the
+/// [`ParseError`](crate::ParseError) message.
+#[Scalar(name = "Identifier")]
+impl<T> ScalarType for Identifier<T> {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::String(s) => Ok(Identifier::parse_str(s)?),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn is_valid(value: &Value) -> bool {
+        matches!(value, Value::String(_))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.as_str().to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_graphql::{ScalarType, Value};
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn value_round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let value = id.to_value();
+        assert_eq!(value, Value::String("game:item/sword".into()));
+        assert_eq!(
+            <IdentifierUntyped as ScalarType>::parse(value).unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn parse_error_is_reported() {
+        let err = <IdentifierUntyped as ScalarType>::parse(Value::String(
+            "game:Stone".into(),
+        ))
+        .unwrap_err();
+        let message = err.into_server_error(Default::default()).message;
+        assert!(message.contains("illegal character(s) in value"));
+    }
+
+    #[test]
+    fn rejects_non_strings() {
+        assert!(
+            <IdentifierUntyped as ScalarType>::parse(Value::Boolean(true))
+                .is_err()
+        );
+    }
+}
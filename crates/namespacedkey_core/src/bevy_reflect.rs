@@ -0,0 +1,75 @@
+//! Bevy reflection support. Identifiers are reflected as opaque values, so
+//! they work in reflected components and round-trip through scene
+//! serialization using their serde representation.
+
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize, impl_reflect_opaque};
+
+use crate::Identifier;
+
+impl_reflect_opaque!((in namespacedkey_core) Identifier<T>(
+    Clone,
+    Hash,
+    PartialEq,
+    Serialize,
+    Deserialize
+));
+
+#[cfg(test)]
+mod tests {
+    use bevy_reflect::{
+        FromReflect, PartialReflect, Reflect, TypePath, TypeRegistry,
+        serde::{ReflectSerializer, TypedReflectDeserializer},
+    };
+    use serde::de::DeserializeSeed;
+
+    use crate::{Identifier, IdentifierUntyped};
+
+    #[derive(TypePath)]
+    struct Block;
+
+    #[test]
+    fn type_path_includes_marker() {
+        assert_eq!(
+            Identifier::<Block>::type_path(),
+            format!("namespacedkey_core::Identifier<{}>", Block::type_path())
+        );
+    }
+
+    #[test]
+    fn reflect_clone_and_compare() {
+        let id = IdentifierUntyped::parse("game:stone").unwrap();
+        let reflected: Box<dyn Reflect> = Box::new(id.clone());
+        assert_eq!(
+            reflected.reflect_partial_eq(id.as_partial_reflect()),
+            Some(true)
+        );
+        let cloned = reflected.reflect_clone().unwrap();
+        assert_eq!(IdentifierUntyped::from_reflect(&*cloned), Some(id));
+    }
+
+    #[test]
+    fn scene_serialization_round_trip() {
+        let mut registry = TypeRegistry::default();
+        registry.register::<IdentifierUntyped>();
+
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let serializer = ReflectSerializer::new(&id, &registry);
+        let json = serde_json::to_string(&serializer).unwrap();
+        assert_eq!(
+            json,
+            r#"{"namespacedkey_core::Identifier<()>":"game:item/sword"}"#
+        );
+
+        let registration = registry
+            .get(std::any::TypeId::of::<IdentifierUntyped>())
+            .unwrap();
+        let deserializer =
+            TypedReflectDeserializer::new(registration, &registry);
+        let value = deserializer
+            .deserialize(&mut serde_json::Deserializer::from_str(
+                r#""game:item/sword""#,
+            ))
+            .unwrap();
+        assert_eq!(IdentifierUntyped::from_reflect(&*value), Some(id));
+    }
+}
@@ -0,0 +1,82 @@
+//! Native bincode 2 support. Identifiers are encoded as two length-prefixed
+//! strings, the same layout the serde impls use for binary formats.
+
+use bincode::{
+    BorrowDecode, Decode, Encode,
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+};
+
+use crate::{Identifier, ParseError};
+
+fn decode_error(err: ParseError) -> DecodeError {
+    DecodeError::OtherString(err.to_string())
+}
+
+impl<T> Encode for Identifier<T> {
+    fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+        self.namespace().encode(encoder)?;
+        self.value().encode(encoder)
+    }
+}
+
+impl<Context, T> Decode<Context> for Identifier<T> {
+    fn decode<D: Decoder<Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let namespace = String::decode(decoder)?;
+        let value = String::decode(decoder)?;
+        Identifier::new(namespace, value).map_err(decode_error)
+    }
+}
+
+impl<'de, Context, T> BorrowDecode<'de, Context> for Identifier<T> {
+    fn borrow_decode<D: BorrowDecoder<'de, Context = Context>>(
+        decoder: &mut D,
+    ) -> Result<Self, DecodeError> {
+        let namespace = <&str>::borrow_decode(decoder)?;
+        let value = <&str>::borrow_decode(decoder)?;
+        Identifier::new(namespace, value).map_err(decode_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bincode::config;
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let bytes = bincode::encode_to_vec(&id, config::standard()).unwrap();
+        let (decoded, read): (IdentifierUntyped, _) =
+            bincode::decode_from_slice(&bytes, config::standard()).unwrap();
+        assert_eq!(decoded, id);
+        assert_eq!(read, bytes.len());
+    }
+
+    #[test]
+    fn borrow_decode_round_trip() {
+        let id = IdentifierUntyped::parse("game:stone").unwrap();
+        let bytes = bincode::encode_to_vec(&id, config::standard()).unwrap();
+        let (decoded, _): (IdentifierUntyped, _) =
+            bincode::borrow_decode_from_slice(&bytes, config::standard())
+                .unwrap();
+        assert_eq!(decoded, id);
+    }
+
+    #[test]
+    fn decode_rejects_illegal_chars() {
+        let bytes =
+            bincode::encode_to_vec(("game", "Stone"), config::standard())
+                .unwrap();
+        let err = bincode::decode_from_slice::<IdentifierUntyped, _>(
+            &bytes,
+            config::standard(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("illegal character(s) in value"));
+    }
+}
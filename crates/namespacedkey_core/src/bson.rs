@@ -0,0 +1,99 @@
+//! Conversions between [`Identifier`] and [`Bson`] values.
+//!
+//! Identifiers are stored as BSON strings holding their `namespace:value`
+//! form, so they can also be used directly as document keys. Keep in mind
+//! that MongoDB query paths treat `.` as a field separator, so keys whose
+//! namespace or value contain a `.` can only be matched as whole documents
+//! (e.g. through `$getField`), not with dotted query paths.
+//!
+//! [`Identifier`]: crate::Identifier
+
+use bson::{Bson, spec::ElementType};
+
+use crate::{Identifier, ParseError};
+
+/// Error returned when a [`Bson`] value cannot be converted into an
+/// [`Identifier`].
+#[derive(Debug, thiserror::Error)]
+pub enum FromBsonError {
+    /// The value is not a BSON string.
+    #[error("expected a BSON string, found {0:?}")]
+    NotAString(ElementType),
+
+    /// The string is not a valid identifier.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+impl<T> From<Identifier<T>> for Bson {
+    fn from(id: Identifier<T>) -> Bson {
+        Bson::String(id.as_str().to_owned())
+    }
+}
+
+impl<T> TryFrom<&Bson> for Identifier<T> {
+    type Error = FromBsonError;
+    fn try_from(value: &Bson) -> Result<Self, Self::Error> {
+        match value {
+            Bson::String(s) => Ok(Identifier::parse_str(s)?),
+            other => Err(FromBsonError::NotAString(other.element_type())),
+        }
+    }
+}
+
+impl<T> TryFrom<Bson> for Identifier<T> {
+    type Error = FromBsonError;
+    fn try_from(value: Bson) -> Result<Self, Self::Error> {
+        Identifier::try_from(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bson::{Bson, Document, doc};
+
+    use super::FromBsonError;
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[test]
+    fn round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let bson = Bson::from(&id);
+        assert_eq!(bson, Bson::String("game:item/sword".into()));
+        assert_eq!(IdentifierUntyped::try_from(bson).unwrap(), id);
+    }
+
+    #[test]
+    fn rejects_non_strings() {
+        let err = IdentifierUntyped::try_from(Bson::Int32(1)).unwrap_err();
+        assert!(matches!(err, FromBsonError::NotAString(_)));
+    }
+
+    #[test]
+    fn rejects_invalid_strings() {
+        let err = IdentifierUntyped::try_from(Bson::String("game:".into()))
+            .unwrap_err();
+        assert!(matches!(err, FromBsonError::Parse(ParseError::EmptyValue)));
+    }
+
+    #[test]
+    fn identifiers_as_document_keys() {
+        let stone = IdentifierUntyped::parse("game:stone").unwrap();
+        let dotted = IdentifierUntyped::parse("my.mod:blocks/v1.2").unwrap();
+        let doc = doc! {
+            stone.to_string(): 1,
+            dotted.to_string(): 2,
+        };
+
+        let mut bytes = Vec::new();
+        doc.to_writer(&mut bytes).unwrap();
+        let decoded = Document::from_reader(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, doc);
+
+        let keys: Vec<IdentifierUntyped> = decoded
+            .keys()
+            .map(|key| IdentifierUntyped::parse(key.as_str()).unwrap())
+            .collect();
+        assert_eq!(keys, vec![stone, dotted]);
+    }
+}
@@ -0,0 +1,176 @@
+//! Parsing many identifiers at once, e.g. from a manifest or a
+//! comma-separated list.
+
+use std::{collections::HashMap, ops::Range};
+
+use crate::{
+    Charset, DEFAULT_NAMESPACE, DEFAULT_SEPARATOR, Identifier, ParseError,
+    Rules, intern::Namespace,
+};
+
+/// Error returned by [`Identifier::parse_list`], locating the bad element.
+#[derive(Debug, thiserror::Error)]
+#[error("element {index} at {span:?}: {error}")]
+pub struct ListError {
+    /// The 0-based index of the element.
+    pub index: usize,
+    /// The byte range of the trimmed element in the original string.
+    pub span: Range<usize>,
+    /// Why the element is invalid.
+    #[source]
+    pub error: ParseError,
+}
+
+impl<T> Identifier<T> {
+    /// Parses a comma-separated list of identifiers, trimming whitespace
+    /// around each element.
+    ///
+    /// A blank string is an empty list. Otherwise every element must be a
+    /// valid identifier, so `a:b,,c:d` and `a:b,` fail with
+    /// [`ParseError::EmptyValue`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let ids = IdentifierUntyped::parse_list("a:b, c:d,e").unwrap();
+    /// assert_eq!(ids.len(), 3);
+    ///
+    /// let err = IdentifierUntyped::parse_list("a:b, C:d").unwrap_err();
+    /// assert_eq!((err.index, err.span), (1, 5..8));
+    /// ```
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, ListError> {
+        if s.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut start = 0;
+        s.split(',')
+            .enumerate()
+            .map(|(index, element)| {
+                let end = start + element.len();
+                let leading = element.len() - element.trim_start().len();
+                let trimmed = element.trim();
+                let span = start + leading..start + leading + trimmed.len();
+                start = end + 1;
+                Identifier::parse_str(trimmed).map_err(|error| ListError {
+                    index,
+                    span,
+                    error,
+                })
+            })
+            .collect()
+    }
+
+    /// Parses every string in `inputs` like [`Identifier::parse`], pairing
+    /// each error with the index of the input that caused it.
+    ///
+    /// The charsets and limits are looked up once, and each distinct
+    /// namespace is interned once for the whole batch rather than once per
+    /// identifier, which avoids contending on the global interner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let results = IdentifierUntyped::parse_many(["game:stone", "game:Dirt"]);
+    /// assert!(results[0].is_ok());
+    /// assert!(matches!(results[1], Err((1, _))));
+    /// ```
+    pub fn parse_many<I>(inputs: I) -> Vec<Result<Self, (usize, ParseError)>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let rules = Rules::global(Charset::STRICT);
+        let mut namespaces: HashMap<String, Namespace> = HashMap::new();
+
+        let inputs = inputs.into_iter();
+        let mut results = Vec::with_capacity(inputs.size_hint().0);
+        for (idx, input) in inputs.enumerate() {
+            let input = input.as_ref();
+            let (namespace, value) =
+                input.split_once(DEFAULT_SEPARATOR).unwrap_or(("", input));
+            let result = rules
+                .check_ascii(input)
+                .and_then(|()| rules.check(namespace, value))
+                .map(|()| {
+                    let namespace = if namespace.is_empty() {
+                        DEFAULT_NAMESPACE
+                    } else {
+                        namespace
+                    };
+                    let namespace = match namespaces.get(namespace) {
+                        Some(interned) => interned.clone(),
+                        None => {
+                            let interned = Namespace::new(namespace);
+                            namespaces
+                                .insert(namespace.to_owned(), interned.clone());
+                            interned
+                        }
+                    };
+                    Identifier::from_parts(namespace, value)
+                });
+            results.push(result.map_err(|err| (idx, err)));
+        }
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[test]
+    fn matches_parse() {
+        let inputs =
+            ["game:stone", "stone", ":dirt", "game:", "B@d:x", "a:b:c"];
+        let results = IdentifierUntyped::parse_many(inputs);
+        assert_eq!(results.len(), inputs.len());
+        for (idx, (input, result)) in inputs.iter().zip(results).enumerate() {
+            match (IdentifierUntyped::parse(*input), result) {
+                (Ok(expected), Ok(actual)) => assert_eq!(actual, expected),
+                (Err(expected), Err((at, actual))) => {
+                    assert_eq!(at, idx);
+                    assert_eq!(actual.to_string(), expected.to_string());
+                }
+                (expected, actual) => {
+                    panic!("{input}: expected {expected:?}, got {actual:?}")
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn parse_list() {
+        let ids = IdentifierUntyped::parse_list(" a:b ,c:d,\te ").unwrap();
+        let ids: Vec<_> = ids.iter().map(ToString::to_string).collect();
+        assert_eq!(ids, ["a:b", "c:d", "unspecified:e"]);
+        assert!(IdentifierUntyped::parse_list("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn parse_list_errors() {
+        let input = "a:b, x:Bad ,c";
+        let err = IdentifierUntyped::parse_list(input).unwrap_err();
+        assert_eq!(err.index, 1);
+        assert_eq!(&input[err.span.clone()], "x:Bad");
+        assert!(matches!(err.error, ParseError::IllegalCharsInValue(..)));
+
+        let err = IdentifierUntyped::parse_list("a:b,,c").unwrap_err();
+        assert_eq!((err.index, err.span), (1, 4..4));
+        let err = IdentifierUntyped::parse_list("a:b, ").unwrap_err();
+        assert_eq!((err.index, err.span), (1, 5..5));
+        assert!(matches!(err.error, ParseError::EmptyValue));
+    }
+
+    #[test]
+    fn owned_inputs() {
+        let lines = vec![String::from("game:stone"), String::from("game:")];
+        let results = IdentifierUntyped::parse_many(&lines);
+        assert_eq!(results[0].as_ref().unwrap().value(), "stone");
+        assert!(matches!(results[1], Err((1, ParseError::EmptyValue))));
+    }
+}
@@ -0,0 +1,114 @@
+//! A single canonical spelling for values that are used as paths, so that
+//! `item//sword/` and `item/./sword` end up as the same cache key.
+
+use crate::Identifier;
+
+/// Error returned when a value cannot be canonicalized.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CanonicalizeError {
+    /// A `..` segment at this byte index of the value goes above the root.
+    #[error("`..` at index {0} escapes the root")]
+    EscapesRoot(usize),
+
+    /// Nothing is left of the value after resolving `.` and `..`.
+    #[error("value is empty once canonicalized")]
+    Empty,
+}
+
+impl<T> Identifier<T> {
+    /// Returns this identifier with its value in canonical form: runs of
+    /// `/` collapsed, leading and trailing `/` removed and `.`/`..` segments
+    /// resolved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id =
+    ///     IdentifierUntyped::parse("game:/item//./old/../sword/").unwrap();
+    /// assert_eq!(id.canonicalize().unwrap().value(), "item/sword");
+    /// ```
+    pub fn canonicalize(&self) -> Result<Self, CanonicalizeError> {
+        let mut segments: Vec<&str> = Vec::new();
+        let mut idx = 0;
+        for segment in self.value().split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => {
+                    if segments.pop().is_none() {
+                        return Err(CanonicalizeError::EscapesRoot(idx));
+                    }
+                }
+                segment => segments.push(segment),
+            }
+            idx += segment.len() + 1;
+        }
+        if segments.is_empty() {
+            return Err(CanonicalizeError::Empty);
+        }
+        Ok(Identifier::from_parts(
+            self.full.namespace.clone(),
+            &segments.join("/"),
+        ))
+    }
+
+    /// Returns `true` if the value is already in the form
+    /// [`canonicalize`](Self::canonicalize) produces.
+    pub fn is_canonical(&self) -> bool {
+        self.value()
+            .split('/')
+            .all(|segment| !matches!(segment, "" | "." | ".."))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CanonicalizeError;
+    use crate::IdentifierUntyped;
+
+    fn canonical(s: &str) -> Result<String, CanonicalizeError> {
+        IdentifierUntyped::parse(s)
+            .unwrap()
+            .canonicalize()
+            .map(|id| id.into_value())
+    }
+
+    #[test]
+    fn resolves_paths() {
+        assert_eq!(canonical("game:item//sword/").unwrap(), "item/sword");
+        assert_eq!(canonical("game:./a/b/../c").unwrap(), "a/c");
+        assert_eq!(canonical("game:..a/.b/c..").unwrap(), "..a/.b/c..");
+    }
+
+    #[test]
+    fn keeps_namespace() {
+        let id = IdentifierUntyped::parse("game:a//b").unwrap();
+        assert_eq!(id.canonicalize().unwrap().namespace(), "game");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            canonical("game:a/../../b"),
+            Err(CanonicalizeError::EscapesRoot(5))
+        );
+        assert_eq!(canonical("game:a/.."), Err(CanonicalizeError::Empty));
+        assert_eq!(canonical("game:/"), Err(CanonicalizeError::Empty));
+    }
+
+    #[test]
+    fn is_canonical() {
+        for input in ["game:a", "game:a/b", "game:a.b/.c"] {
+            let id = IdentifierUntyped::parse(input).unwrap();
+            assert!(id.is_canonical(), "{input}");
+            assert_eq!(id.canonicalize().unwrap(), id);
+        }
+        for input in
+            ["game:/a", "game:a/", "game:a//b", "game:a/./b", "game:.."]
+        {
+            let id = IdentifierUntyped::parse(input).unwrap();
+            assert!(!id.is_canonical(), "{input}");
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! [clap] argument parsing for identifiers.
+//!
+//! [`Identifier`] implements [`ValueParserFactory`], so `value_parser!` picks
+//! up [`IdentifierValueParser`] automatically:
+//!
+//! ```
+//! use clap::{Arg, Command, value_parser};
+//! use namespacedkey_core::IdentifierUntyped;
+//!
+//! let cmd = Command::new("give").arg(
+//!     Arg::new("item").value_parser(value_parser!(IdentifierUntyped)),
+//! );
+//! let matches = cmd.try_get_matches_from(["give", "game:stone"]).unwrap();
+//! let item = matches.get_one::<IdentifierUntyped>("item").unwrap();
+//! assert_eq!(item.to_string(), "game:stone");
+//! ```
+//!
+//! Invalid input is reported with the offending characters underlined, as
+//! rendered by [`ParseError::underline`](crate::ParseError::underline).
+//!
+//! [clap]: https://docs.rs/clap/latest/clap/
+
+use std::{
+    ffi::OsStr,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    marker::PhantomData,
+};
+
+use clap::{
+    Arg, Command, Error,
+    builder::{TypedValueParser, ValueParserFactory},
+    error::ErrorKind,
+};
+
+use crate::Identifier;
+
+/// A [`TypedValueParser`] producing [`Identifier`]s.
+pub struct IdentifierValueParser<T>(PhantomData<fn() -> T>);
+
+impl<T> IdentifierValueParser<T> {
+    /// Creates a new parser.
+    pub fn new() -> Self {
+        IdentifierValueParser(PhantomData)
+    }
+}
+
+impl<T> Default for IdentifierValueParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for IdentifierValueParser<T> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for IdentifierValueParser<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str("IdentifierValueParser")
+    }
+}
+
+impl<T: 'static> TypedValueParser for IdentifierValueParser<T> {
+    type Value = Identifier<T>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        let input = value
+            .to_str()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd))?;
+        Identifier::parse_str(input).map_err(|err| {
+            let arg = arg.map_or_else(|| "...".to_owned(), Arg::to_string);
+            let underline = err.underline(input).replace('\n', "\n  ");
+            let message = format!(
+                "invalid value '{input}' for '{arg}': {err}\n\n  {underline}"
+            );
+            Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+        })
+    }
+}
+
+impl<T: 'static> ValueParserFactory for Identifier<T> {
+    type Parser = IdentifierValueParser<T>;
+
+    fn value_parser() -> Self::Parser {
+        IdentifierValueParser::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{Arg, Command, error::ErrorKind, value_parser};
+
+    use crate::IdentifierUntyped;
+
+    fn command() -> Command {
+        Command::new("give").arg(
+            Arg::new("item")
+                .required(true)
+                .value_parser(value_parser!(IdentifierUntyped)),
+        )
+    }
+
+    #[test]
+    fn parses_valid_argument() {
+        let matches = command()
+            .try_get_matches_from(["give", "game:item/sword"])
+            .unwrap();
+        let item = matches.get_one::<IdentifierUntyped>("item").unwrap();
+        assert_eq!(item.to_string(), "game:item/sword");
+    }
+
+    #[test]
+    fn invalid_argument_is_underlined() {
+        let err = command()
+            .try_get_matches_from(["give", "game:Sto!ne"])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        let rendered = err.to_string();
+        assert!(rendered.contains("invalid value 'game:Sto!ne' for '<item>'"));
+        assert!(rendered.contains("\n  game:Sto!ne\n       ^  ^"));
+    }
+}
@@ -0,0 +1,146 @@
+//! Non-fatal warnings about identifiers that are legal but suspicious, for
+//! linting resource packs and config files.
+
+use crate::{Identifier, ParseError};
+
+/// Values longer than this many bytes get a [`Warning::LongValue`].
+pub const LONG_VALUE_THRESHOLD: usize = 128;
+
+/// A suspicious but legal pattern in an identifier's value.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Warning {
+    /// Two `/` in a row at this byte index of the value, giving an empty
+    /// path segment.
+    #[error("double slash at index {0}")]
+    DoubleSlash(usize),
+
+    /// The value starts with `/`.
+    #[error("leading slash")]
+    LeadingSlash,
+
+    /// The value ends with `/`.
+    #[error("trailing slash")]
+    TrailingSlash,
+
+    /// A path segment mixes `l` with `1`, or `o` with `0`, which are easy to
+    /// confuse in many fonts.
+    #[error("segment {0:?} mixes easily confused characters")]
+    Confusable(String),
+
+    /// The value is longer than [`LONG_VALUE_THRESHOLD`] bytes.
+    #[error("value is {0} bytes long")]
+    LongValue(usize),
+}
+
+impl<T> Identifier<T> {
+    /// Parses a string like [`Identifier::parse`] and also returns a
+    /// [`Warning`] for every suspicious pattern in the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::{IdentifierUntyped, diagnostics::Warning};
+    ///
+    /// let (id, warnings) =
+    ///     IdentifierUntyped::parse_with_diagnostics("game:item//sword/")
+    ///         .unwrap();
+    /// assert_eq!(id.value(), "item//sword/");
+    /// assert_eq!(
+    ///     warnings,
+    ///     [Warning::DoubleSlash(4), Warning::TrailingSlash]
+    /// );
+    /// ```
+    pub fn parse_with_diagnostics(
+        s: &str,
+    ) -> Result<(Self, Vec<Warning>), ParseError> {
+        let id = Self::parse_str(s)?;
+        let warnings = lint(id.value());
+        Ok((id, warnings))
+    }
+}
+
+fn lint(value: &str) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    if value.starts_with('/') {
+        warnings.push(Warning::LeadingSlash);
+    }
+    warnings.extend(
+        value
+            .as_bytes()
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair == b"//")
+            .map(|(idx, _)| Warning::DoubleSlash(idx)),
+    );
+    if value.len() > 1 && value.ends_with('/') {
+        warnings.push(Warning::TrailingSlash);
+    }
+
+    for segment in value.split('/') {
+        let mixes = |a, b| segment.contains(a) && segment.contains(b);
+        if mixes('l', '1') || mixes('o', '0') {
+            warnings.push(Warning::Confusable(segment.to_owned()));
+        }
+    }
+
+    if value.len() > LONG_VALUE_THRESHOLD {
+        warnings.push(Warning::LongValue(value.len()));
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LONG_VALUE_THRESHOLD, Warning};
+    use crate::{IdentifierUntyped, ParseError};
+
+    fn warnings(s: &str) -> Vec<Warning> {
+        IdentifierUntyped::parse_with_diagnostics(s).unwrap().1
+    }
+
+    #[test]
+    fn clean_input_has_no_warnings() {
+        assert!(warnings("game:item/iron_sword_2").is_empty());
+    }
+
+    #[test]
+    fn slashes() {
+        assert_eq!(
+            warnings("game:/a///b"),
+            [
+                Warning::LeadingSlash,
+                Warning::DoubleSlash(2),
+                Warning::DoubleSlash(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn confusables() {
+        assert_eq!(
+            warnings("game:items/l1ght/b0ok"),
+            [
+                Warning::Confusable("l1ght".to_owned()),
+                Warning::Confusable("b0ok".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn long_value() {
+        let input = format!("game:{}", "a".repeat(LONG_VALUE_THRESHOLD + 1));
+        assert_eq!(
+            warnings(&input),
+            [Warning::LongValue(LONG_VALUE_THRESHOLD + 1)]
+        );
+    }
+
+    #[test]
+    fn errors_still_fail() {
+        assert!(matches!(
+            IdentifierUntyped::parse_with_diagnostics("game:"),
+            Err(ParseError::EmptyValue)
+        ));
+    }
+}
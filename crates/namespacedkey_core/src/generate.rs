@@ -0,0 +1,86 @@
+//! What the `arbitrary`, `quickcheck` and `proptest` generators draw from:
+//! the configured legal characters and how long each part may get under the
+//! configured [length limits](crate::limits).
+
+use std::collections::HashSet;
+
+use crate::{
+    DEFAULT_NAMESPACE, DEFAULT_SEPARATOR, legal_namespace_chars,
+    legal_value_chars,
+    limits::{LengthLimits, length_limits},
+};
+
+/// The configured legal characters, sorted so that generation depends only
+/// on the source of randomness, and the configured limits.
+pub(crate) struct Alphabet {
+    pub(crate) namespace: Vec<char>,
+    pub(crate) value: Vec<char>,
+    limits: LengthLimits,
+}
+
+impl Alphabet {
+    pub(crate) fn configured() -> Self {
+        Self {
+            namespace: sorted(legal_namespace_chars()),
+            value: sorted(legal_value_chars()),
+            limits: *length_limits(),
+        }
+    }
+
+    /// The fewest chars a generated namespace may have: 0 if the default
+    /// namespace an empty one becomes leaves room for a value, else 1.
+    pub(crate) fn namespace_min(&self) -> usize {
+        usize::from(self.value_max("").is_some_and(|max| max == 0))
+    }
+
+    /// The most bytes a namespace may take while leaving room for a
+    /// one-byte value.
+    pub(crate) fn namespace_max(&self) -> Option<usize> {
+        let room = self.limits.identifier.map(|limit| {
+            limit.saturating_sub(DEFAULT_SEPARATOR.len_utf8() + 1)
+        });
+        min(self.limits.namespace, room)
+    }
+
+    /// The most bytes a value may take after `namespace`.
+    pub(crate) fn value_max(&self, namespace: &str) -> Option<usize> {
+        let namespace = if namespace.is_empty() {
+            DEFAULT_NAMESPACE
+        } else {
+            namespace
+        };
+        let room = self.limits.identifier.map(|limit| {
+            limit.saturating_sub(namespace.len() + DEFAULT_SEPARATOR.len_utf8())
+        });
+        min(self.limits.value, room)
+    }
+}
+
+/// Collects `chars` up to the first one that would take the string past
+/// `max` bytes.
+pub(crate) fn capped(
+    chars: impl IntoIterator<Item = char>,
+    max: Option<usize>,
+) -> String {
+    let mut s = String::new();
+    for ch in chars {
+        if max.is_some_and(|max| s.len() + ch.len_utf8() > max) {
+            break;
+        }
+        s.push(ch);
+    }
+    s
+}
+
+fn sorted(set: &HashSet<char>) -> Vec<char> {
+    let mut chars: Vec<_> = set.iter().copied().collect();
+    chars.sort_unstable();
+    chars
+}
+
+fn min(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
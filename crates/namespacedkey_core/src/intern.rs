@@ -0,0 +1,539 @@
+//! The interners backing namespaces.
+//!
+//! By default namespaces live in the global [`Intern`] arena and are never
+//! freed. With the `arc-intern` feature they are reference counted with
+//! [`ArcIntern`] instead, and freed once the last identifier using them is
+//! dropped. With the `no-intern` feature nothing is interned globally:
+//! every identifier allocates its own namespace, which is freed along with
+//! it.
+//!
+//! An [`Interner`] keeps its own namespaces apart from the global ones, so
+//! that e.g. each tenant of a multi-tenant host only pays for its own.
+//!
+//! [`interner_stats`] and [`Interner::stats`] report what has been interned,
+//! for monitoring memory growth from user-provided keys.
+//!
+//! Whole identifiers can also be interned into an [`IdentifierHandle`], a
+//! `Copy` key for ECS-style storage.
+//!
+//! [`Intern`]: https://docs.rs/internment/latest/internment/struct.Intern.html
+//! [`ArcIntern`]: https://docs.rs/internment/latest/internment/struct.ArcIntern.html
+
+use std::{
+    borrow::Borrow,
+    cell::RefCell,
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError},
+};
+
+#[cfg(all(feature = "arc-intern", not(feature = "no-intern")))]
+use internment::ArcIntern;
+#[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+use internment::Intern;
+
+use crate::{Identifier, IdentifierUntyped};
+
+thread_local! {
+    /// The interner bound by [`Interner::enter`] on this thread, if any.
+    static SCOPE: RefCell<Option<Interner>> = const { RefCell::new(None) };
+}
+
+/// An interned namespace. Equal namespaces from the same interner share one
+/// allocation.
+#[derive(Clone)]
+pub(crate) struct Namespace {
+    repr: Repr,
+}
+
+#[derive(Clone)]
+enum Repr {
+    #[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+    Global(Intern<str>),
+    // `ArcIntern` cannot intern unsized values.
+    #[cfg(all(feature = "arc-intern", not(feature = "no-intern")))]
+    Global(ArcIntern<String>),
+    // `Arc<String>` rather than `Arc<str>` keeps this variant thin. Also
+    // used for uninterned namespaces under `no-intern`.
+    Scoped(Arc<String>),
+}
+
+impl Namespace {
+    /// Interns `namespace` in the interner bound to this thread, or the
+    /// global one, allocating only if it is not interned there yet. Under
+    /// `no-intern`, always allocates unless an interner is bound.
+    pub(crate) fn new(namespace: &str) -> Self {
+        let scoped = SCOPE.with(|scope| {
+            scope
+                .borrow()
+                .as_ref()
+                .map(|interner| interner.intern(namespace))
+        });
+        let repr = match scoped {
+            Some(scoped) => Repr::Scoped(scoped),
+            #[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+            None => Repr::Global(intern_global(namespace)),
+            #[cfg(all(feature = "arc-intern", not(feature = "no-intern")))]
+            None => Repr::Global(ArcIntern::from_ref(namespace)),
+            #[cfg(feature = "no-intern")]
+            None => Repr::Scoped(Arc::new(namespace.to_owned())),
+        };
+        Self { repr }
+    }
+
+    /// Returns the namespace as a string slice.
+    pub(crate) fn as_str(&self) -> &str {
+        match &self.repr {
+            #[cfg(not(feature = "no-intern"))]
+            Repr::Global(global) => global,
+            Repr::Scoped(scoped) => scoped,
+        }
+    }
+}
+
+/// The namespaces this crate added to the global arena. The arena can't
+/// list its contents, and it is shared with other crates interning `str`s.
+#[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+static ADDED: OnceLock<Mutex<HashSet<Intern<str>>>> = OnceLock::new();
+
+#[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+fn added() -> MutexGuard<'static, HashSet<Intern<str>>> {
+    // Namespaces are inserted whole, so a poisoned lock is fine.
+    ADDED
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Interns `namespace` globally, recording it in [`ADDED`] if it is new to
+/// the arena. Known namespaces never touch the record.
+#[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+fn intern_global(namespace: &str) -> Intern<str> {
+    if Intern::<str>::is_interned(namespace) {
+        return Intern::from(namespace);
+    }
+    let interned = Intern::from(namespace);
+    // Another thread may have added it since the check; the set keeps one.
+    added().insert(interned);
+    interned
+}
+
+impl Deref for Namespace {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Namespaces from the same interner compare by pointer, others by
+/// contents.
+impl PartialEq for Namespace {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(not(feature = "no-intern"))]
+        if let (Repr::Global(a), Repr::Global(b)) = (&self.repr, &other.repr) {
+            return a == b;
+        }
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Namespace {}
+
+/// Hashes the characters, like [`str`], so that equal namespaces from
+/// different interners hash the same.
+impl Hash for Namespace {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl PartialOrd for Namespace {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Namespace {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Debug for Namespace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// A namespace interner separate from the global one.
+///
+/// Identifiers built inside [`enter`](Interner::enter) intern their
+/// namespaces here instead of globally. The namespaces are freed once the
+/// interner and every identifier built with it are dropped. Cloning an
+/// `Interner` gives another handle to the same one.
+///
+/// Identifiers from different interners still compare equal and hash the
+/// same when their namespaces and values are, so they can share a map.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, intern::Interner};
+///
+/// let tenant = Interner::new();
+/// let id = tenant.enter(|| IdentifierUntyped::parse("tenant:stone"))
+///     .unwrap();
+/// assert_eq!(tenant.len(), 1);
+/// assert_eq!(id, IdentifierUntyped::parse("tenant:stone").unwrap());
+/// ```
+#[derive(Clone, Default)]
+pub struct Interner {
+    namespaces: Arc<Mutex<HashSet<Key>>>,
+}
+
+/// Lets the set be queried with a `&str`.
+#[derive(PartialEq, Eq, Hash)]
+struct Key(Arc<String>);
+
+impl Borrow<str> for Key {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f` with this interner bound to the current thread, so every
+    /// identifier `f` builds on this thread interns its namespace here.
+    ///
+    /// Calls can be nested; the innermost interner wins, and the previous
+    /// one is restored when `f` returns or panics.
+    pub fn enter<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<Interner>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                let previous = self.0.take();
+                SCOPE.with(|scope| *scope.borrow_mut() = previous);
+            }
+        }
+
+        let previous = SCOPE.with(|scope| scope.replace(Some(self.clone())));
+        let _restore = Restore(previous);
+        f()
+    }
+
+    /// Returns the number of namespaces interned here.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no namespace has been interned here.
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Returns statistics about the namespaces interned here.
+    pub fn stats(&self) -> InternerStats {
+        let namespaces = self.lock();
+        InternerStats::new(namespaces.iter().map(|Key(namespace)| Namespace {
+            repr: Repr::Scoped(Arc::clone(namespace)),
+        }))
+    }
+
+    fn intern(&self, namespace: &str) -> Arc<String> {
+        let mut namespaces = self.lock();
+        if let Some(Key(existing)) = namespaces.get(namespace) {
+            return Arc::clone(existing);
+        }
+        let interned = Arc::new(namespace.to_owned());
+        namespaces.insert(Key(Arc::clone(&interned)));
+        interned
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashSet<Key>> {
+        // The set is never left half-updated, so a poisoned lock is fine.
+        self.namespaces
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Debug for Interner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Interner")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A snapshot of what an interner holds, from [`interner_stats`] or
+/// [`Interner::stats`].
+#[derive(Debug, Clone, Default)]
+pub struct InternerStats {
+    namespaces: Vec<Namespace>,
+    bytes: usize,
+}
+
+impl InternerStats {
+    fn new(namespaces: impl Iterator<Item = Namespace>) -> Self {
+        let namespaces: Vec<_> = namespaces.collect();
+        let bytes = namespaces.iter().map(|namespace| namespace.len()).sum();
+        Self { namespaces, bytes }
+    }
+
+    /// Returns the number of interned namespaces.
+    pub fn len(&self) -> usize {
+        self.namespaces.len()
+    }
+
+    /// Returns `true` if no namespace was interned.
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+    }
+
+    /// Returns the approximate number of bytes retained: the namespaces'
+    /// lengths, not counting the interner's own bookkeeping.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Returns the interned namespaces, in no particular order.
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> {
+        self.namespaces.iter().map(Namespace::as_str)
+    }
+}
+
+/// Returns statistics about the global interner.
+///
+/// Under `arc-intern` unused namespaces are freed and nothing keeps track
+/// of the rest, and under `no-intern` nothing is interned, so the result is
+/// always empty with either feature.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, intern::interner_stats};
+///
+/// let _id = IdentifierUntyped::parse("monitored:key").unwrap();
+/// let stats = interner_stats();
+/// # #[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+/// assert!(stats.namespaces().any(|namespace| namespace == "monitored"));
+/// ```
+pub fn interner_stats() -> InternerStats {
+    #[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+    return InternerStats::new(added().iter().map(|&interned| Namespace {
+        repr: Repr::Global(interned),
+    }));
+    #[cfg(any(feature = "arc-intern", feature = "no-intern"))]
+    InternerStats::default()
+}
+
+static HANDLES: OnceLock<Mutex<Handles>> = OnceLock::new();
+
+/// The global table behind [`IdentifierHandle`]. Entries are never removed.
+#[derive(Default)]
+struct Handles {
+    ids: Vec<IdentifierUntyped>,
+    // Keyed by string so lookups borrow the identifier's `as_str`.
+    indices: HashMap<String, u32>,
+}
+
+fn handles() -> MutexGuard<'static, Handles> {
+    // Entries are pushed and indexed together, so a poisoned lock is fine.
+    HANDLES
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// A `Copy` stand-in for an identifier, from
+/// [`intern_handle`](Identifier::intern_handle).
+///
+/// Handles index a global table that is never freed, so equal identifiers
+/// always get the same handle and [`resolve`](Self::resolve) always
+/// succeeds. Two handles are equal exactly when their identifiers are, so
+/// handles can stand in for identifiers as map keys. They hash their index,
+/// not the identifier, so a handle can't be used to look up an identifier
+/// key or the reverse. [`Ord`] is the order they were first interned in.
+/// Handle numbers differ between runs of the program, so they must not be
+/// persisted or sent elsewhere.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::IdentifierUntyped;
+///
+/// let id = IdentifierUntyped::parse("game:stone").unwrap();
+/// let handle = id.intern_handle().unwrap();
+/// assert_eq!(handle, id.clone().intern_handle().unwrap());
+/// assert_eq!(handle.resolve(), id);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IdentifierHandle(u32);
+
+impl IdentifierHandle {
+    /// Returns the identifier this handle was interned from, with its type
+    /// erased.
+    pub fn resolve(self) -> IdentifierUntyped {
+        // Handles are only made by `intern_handle`, so the entry exists.
+        handles().ids[self.0 as usize].clone()
+    }
+
+    /// Returns the handle's index in the global table.
+    pub fn index(self) -> u32 {
+        self.0
+    }
+}
+
+impl<T> Identifier<T> {
+    /// Interns the whole identifier into the global handle table, or
+    /// returns its existing handle.
+    ///
+    /// Returns `None` only once `u32::MAX` identifiers have been interned.
+    pub fn intern_handle(&self) -> Option<IdentifierHandle> {
+        let mut handles = handles();
+        if let Some(&index) = handles.indices.get(self.as_str()) {
+            return Some(IdentifierHandle(index));
+        }
+        let index = u32::try_from(handles.ids.len()).ok()?;
+        handles.ids.push(self.clone().erase());
+        handles.indices.insert(self.as_str().to_owned(), index);
+        Some(IdentifierHandle(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdentifierHandle, Interner, Namespace, interner_stats};
+    use crate::IdentifierUntyped;
+
+    #[test]
+    #[cfg(not(feature = "no-intern"))]
+    fn equal_namespaces_share_storage() {
+        let a = Namespace::new("game");
+        let b = Namespace::new(&String::from("game"));
+        assert_eq!(a, b);
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+        assert_ne!(a, Namespace::new("other"));
+    }
+
+    #[test]
+    fn scoped_namespaces_stay_in_their_interner() {
+        let interner = Interner::new();
+        let (a, b) = interner
+            .enter(|| (Namespace::new("scoped"), Namespace::new("scoped")));
+        assert!(std::ptr::eq(a.as_str(), b.as_str()));
+        assert_eq!(interner.len(), 1);
+
+        let global = Namespace::new("scoped");
+        assert!(!std::ptr::eq(a.as_str(), global.as_str()));
+        assert_eq!(a, global);
+    }
+
+    #[test]
+    fn scoped_and_global_identifiers_share_a_set() {
+        use std::collections::HashSet;
+
+        let scoped = Interner::new()
+            .enter(|| IdentifierUntyped::parse("shared:stone"))
+            .unwrap();
+        let global = IdentifierUntyped::parse("shared:stone").unwrap();
+        let set: HashSet<_> = [scoped, global].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn nested_scopes_restore_the_outer_one() {
+        let (outer, inner) = (Interner::new(), Interner::new());
+        outer.enter(|| {
+            inner.enter(|| IdentifierUntyped::parse("a:b")).unwrap();
+            IdentifierUntyped::parse("c:d").unwrap();
+        });
+        assert_eq!((outer.len(), inner.len()), (1, 1));
+        assert!(Interner::new().is_empty());
+    }
+
+    #[test]
+    fn scope_is_restored_after_panic() {
+        let interner = Interner::new();
+        let result = std::panic::catch_unwind(|| {
+            interner.enter(|| -> () { std::panic::panic_any("boom") })
+        });
+        assert!(result.is_err());
+        IdentifierUntyped::parse("after:panic").unwrap();
+        assert!(interner.is_empty());
+    }
+
+    #[test]
+    #[cfg(all(feature = "arc-intern", not(feature = "no-intern")))]
+    fn namespaces_are_reference_counted() {
+        use super::Repr;
+
+        let a = Namespace::new("reference_counted");
+        let b = a.clone();
+        let Repr::Global(global) = &a.repr else {
+            unreachable!("no interner is bound");
+        };
+        assert_eq!(global.refcount(), 2);
+        drop(b);
+        assert_eq!(global.refcount(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "no-intern")]
+    fn namespaces_are_not_shared() {
+        let a = Namespace::new("game");
+        let b = Namespace::new("game");
+        assert_eq!(a, b);
+        assert!(!std::ptr::eq(a.as_str(), b.as_str()));
+    }
+
+    #[test]
+    fn handles_round_trip() {
+        let a = IdentifierUntyped::parse("handle:a").unwrap();
+        let b = IdentifierUntyped::parse("handle:b").unwrap();
+        let (ha, hb) = (a.intern_handle().unwrap(), b.intern_handle().unwrap());
+        assert_ne!(ha, hb);
+        assert_eq!(a.intern_handle(), Some(ha));
+        assert_eq!(ha.resolve(), a);
+        assert_eq!(hb.resolve(), b);
+        assert_eq!(IdentifierHandle(ha.index()).resolve().as_str(), "handle:a");
+    }
+
+    #[test]
+    fn stats() {
+        let interner = Interner::new();
+        interner.enter(|| {
+            for input in ["stats:a", "stats:b", "counted:a"] {
+                IdentifierUntyped::parse(input).unwrap();
+            }
+        });
+        let stats = interner.stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats.bytes(), "stats".len() + "counted".len());
+        let mut namespaces: Vec<_> = stats.namespaces().collect();
+        namespaces.sort_unstable();
+        assert_eq!(namespaces, ["counted", "stats"]);
+        assert!(Interner::new().stats().is_empty());
+
+        let _id = IdentifierUntyped::parse("stats_global:x").unwrap();
+        let global = interner_stats();
+        #[cfg(not(any(feature = "arc-intern", feature = "no-intern")))]
+        assert!(
+            global
+                .namespaces()
+                .any(|namespace| namespace == "stats_global")
+        );
+        #[cfg(any(feature = "arc-intern", feature = "no-intern"))]
+        assert!(global.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+//! Appending path segments to a value with `/`, like [`Path::join`].
+//!
+//! [`Path::join`]: std::path::Path::join
+
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    ops::Div,
+};
+
+use crate::{Identifier, ParseError, options::check_structure};
+
+impl<T> Identifier<T> {
+    /// Returns a copy of this identifier with `segment` appended to its
+    /// value after a `/`.
+    ///
+    /// `segment` may itself contain `/`, but must not be empty or start,
+    /// end or contain empty segments. The result is validated like in
+    /// [`Identifier::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:block").unwrap();
+    /// assert_eq!(id.join("stone").unwrap(), "game:block/stone");
+    /// assert!(id.join("").is_err());
+    /// ```
+    pub fn join(&self, segment: &str) -> Result<Self, ParseError> {
+        if segment.is_empty() {
+            return Err(ParseError::TrailingSlash(format!(
+                "{}/",
+                self.value()
+            )));
+        }
+        check_structure(segment)?;
+        self.with_value(&format!("{}/{segment}", self.value()))
+    }
+}
+
+/// Joins segments onto an identifier's value with the `/` operator.
+///
+/// Dividing an [`Identifier`] by a `&str` starts a `ValuePath`, and every
+/// further division appends another segment, as in
+/// [`Identifier::join`]. The first error is kept and returned by
+/// [`finish`](ValuePath::finish).
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::IdentifierUntyped;
+///
+/// let id = IdentifierUntyped::parse("game:block").unwrap();
+/// let north = (&id / "textures" / "north").finish().unwrap();
+/// assert_eq!(north, "game:block/textures/north");
+/// assert!((id / "Bad" / "north").finish().is_err());
+/// ```
+pub struct ValuePath<T> {
+    result: Result<Identifier<T>, ParseError>,
+}
+
+impl<T> ValuePath<T> {
+    /// Returns the joined identifier, or the first error.
+    pub fn finish(self) -> Result<Identifier<T>, ParseError> {
+        self.result
+    }
+}
+
+impl<T> Debug for ValuePath<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match &self.result {
+            Ok(id) => f.debug_tuple("ValuePath").field(&id.as_str()).finish(),
+            Err(err) => f.debug_tuple("ValuePath").field(err).finish(),
+        }
+    }
+}
+
+impl<T> Div<&str> for Identifier<T> {
+    type Output = ValuePath<T>;
+
+    fn div(self, segment: &str) -> ValuePath<T> {
+        ValuePath {
+            result: self.join(segment),
+        }
+    }
+}
+
+impl<T> Div<&str> for &Identifier<T> {
+    type Output = ValuePath<T>;
+
+    fn div(self, segment: &str) -> ValuePath<T> {
+        ValuePath {
+            result: self.join(segment),
+        }
+    }
+}
+
+impl<T> Div<&str> for ValuePath<T> {
+    type Output = ValuePath<T>;
+
+    fn div(self, segment: &str) -> ValuePath<T> {
+        ValuePath {
+            result: self.result.and_then(|id| id.join(segment)),
+        }
+    }
+}
+
+impl<T> From<ValuePath<T>> for Result<Identifier<T>, ParseError> {
+    fn from(path: ValuePath<T>) -> Self {
+        path.result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[test]
+    fn joins_segments() {
+        let id = IdentifierUntyped::parse("game:a").unwrap();
+        assert_eq!(id.join("b/c").unwrap(), "game:a/b/c");
+        assert_eq!((id / "b" / "c/d").finish().unwrap(), "game:a/b/c/d");
+    }
+
+    #[test]
+    fn rejects_empty_segments() {
+        let id = IdentifierUntyped::parse("game:a").unwrap();
+        for (segment, expected) in [
+            ("", "value \"a/\" ends with `/`"),
+            ("/b", "value \"/b\" starts with `/`"),
+            ("b//c", "empty path segment in value \"b//c\" at 2"),
+        ] {
+            let err = id.join(segment).unwrap_err();
+            assert_eq!(err.to_string(), expected, "{segment:?}");
+        }
+    }
+
+    #[test]
+    fn keeps_first_error() {
+        let id = IdentifierUntyped::parse("game:a").unwrap();
+        let err = (&id / "B" / "").finish().unwrap_err();
+        assert!(matches!(err, ParseError::IllegalCharsInValue(..)));
+    }
+}
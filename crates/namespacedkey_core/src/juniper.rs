@@ -0,0 +1,49 @@
+//! Juniper support. Identifiers are exposed as an `Identifier` scalar
+//! holding their `namespace:value` string.
+//!
+//! The scalar is declared on [`Identifier`] itself; this module holds the
+//! conversions it uses.
+
+use juniper::{InputValue, ScalarValue, Value};
+
+use crate::Identifier;
+
+pub(crate) fn to_output<S: ScalarValue, T>(id: &Identifier<T>) -> Value<S> {
+    Value::scalar(id.as_str().to_owned())
+}
+
+pub(crate) fn from_input<S: ScalarValue, T>(
+    input: &InputValue<S>,
+) -> Result<Identifier<T>, String> {
+    let s = input.as_string_value().ok_or_else(|| {
+        format!("Expected `String` for `Identifier`, found: {input}")
+    })?;
+    Identifier::parse_str(s).map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use juniper::{
+        DefaultScalarValue, FromInputValue, InputValue, ToInputValue,
+    };
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn input_round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let input: InputValue<DefaultScalarValue> = id.to_input_value();
+        assert_eq!(input.as_string_value(), Some("game:item/sword"));
+        assert_eq!(IdentifierUntyped::from_input_value(&input).unwrap(), id);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let input = InputValue::<DefaultScalarValue>::scalar("game:Stone");
+        let err = IdentifierUntyped::from_input_value(&input).unwrap_err();
+        assert!(err.message().contains("illegal character(s) in value"));
+
+        let input = InputValue::<DefaultScalarValue>::scalar(1);
+        assert!(IdentifierUntyped::from_input_value(&input).is_err());
+    }
+}
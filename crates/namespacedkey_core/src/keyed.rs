@@ -0,0 +1,635 @@
+//! Values that are named by an identifier.
+
+use std::{
+    any::Any,
+    collections::{BTreeMap, HashMap},
+    fmt::{Debug, Formatter, Result as FmtResult},
+    ops::Index,
+    rc::Rc,
+    sync::Arc,
+};
+
+use crate::Identifier;
+
+/// A value named by an [`Identifier<T>`], like a block named `game:stone`.
+///
+/// The marker defaults to `()`, so `dyn Keyed` names values keyed by an
+/// [`IdentifierUntyped`](crate::IdentifierUntyped). Every identifier is
+/// keyed by itself, and references and smart pointers are keyed by what
+/// they point to.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{Identifier, keyed::Keyed};
+///
+/// struct Block;
+///
+/// struct Stone {
+///     key: Identifier<Block>,
+/// }
+///
+/// impl Keyed<Block> for Stone {
+///     fn key(&self) -> &Identifier<Block> {
+///         &self.key
+///     }
+/// }
+///
+/// let stone = Stone { key: Identifier::parse("game:stone").unwrap() };
+/// assert_eq!(stone.key(), "game:stone");
+/// ```
+pub trait Keyed<T = ()> {
+    /// Returns the identifier naming `self`.
+    fn key(&self) -> &Identifier<T>;
+
+    /// Returns the namespace of the key.
+    fn namespace<'a>(&'a self) -> &'a str
+    where
+        T: 'a,
+    {
+        self.key().namespace()
+    }
+
+    /// Returns the value of the key, the part after the separator.
+    fn value<'a>(&'a self) -> &'a str
+    where
+        T: 'a,
+    {
+        self.key().value()
+    }
+
+    /// Returns the key as an owned `namespace:value` string.
+    fn key_string(&self) -> String {
+        self.key().as_str().to_owned()
+    }
+}
+
+impl<T> Keyed<T> for Identifier<T> {
+    fn key(&self) -> &Identifier<T> {
+        self
+    }
+}
+
+macro_rules! forward_keyed {
+    ($($ty:ty),*) => {$(
+        impl<K: Keyed<T> + ?Sized, T> Keyed<T> for $ty {
+            fn key(&self) -> &Identifier<T> {
+                (**self).key()
+            }
+        }
+    )*};
+}
+
+forward_keyed!(&K, &mut K, Box<K>, Rc<K>, Arc<K>);
+
+/// A [`Keyed`] value that can be downcast to its concrete type.
+trait AnyKeyed<T>: Keyed<T> + Any {}
+
+impl<K: Keyed<T> + Any, T> AnyKeyed<T> for K {}
+
+/// Values of different types, stored by their key, for hosts that don't
+/// know the concrete types their plugins register.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{
+///     IdentifierUntyped,
+///     keyed::{Keyed, KeyedRegistry},
+/// };
+///
+/// struct Command {
+///     key: IdentifierUntyped,
+///     usage: &'static str,
+/// }
+///
+/// impl Keyed for Command {
+///     fn key(&self) -> &IdentifierUntyped {
+///         &self.key
+///     }
+/// }
+///
+/// let key = IdentifierUntyped::parse("plugin:teleport").unwrap();
+/// let mut registry = KeyedRegistry::new();
+/// let command = Command { key: key.clone(), usage: "/tp <x> <y> <z>" };
+/// assert!(registry.register(command).is_ok());
+///
+/// assert_eq!(registry.get(&key).unwrap().key(), "plugin:teleport");
+/// let command = registry.get_as::<Command>(&key).unwrap();
+/// assert_eq!(command.usage, "/tp <x> <y> <z>");
+/// ```
+pub struct KeyedRegistry<T = ()> {
+    values: HashMap<Identifier<T>, Box<dyn AnyKeyed<T>>>,
+}
+
+impl<T: 'static> KeyedRegistry<T> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+
+    /// Adds `value` under its key, or hands it back if the key is taken.
+    pub fn register<K: Keyed<T> + Any>(&mut self, value: K) -> Result<(), K> {
+        if self.values.contains_key(value.key()) {
+            return Err(value);
+        }
+        self.values.insert(value.key().clone(), Box::new(value));
+        Ok(())
+    }
+
+    /// Returns the value registered under `key`.
+    pub fn get(&self, key: &Identifier<T>) -> Option<&dyn Keyed<T>> {
+        self.values.get(key).map(|value| &**value as &dyn Keyed<T>)
+    }
+
+    /// Returns the value registered under `key`, if it is a `K`.
+    pub fn get_as<K: Any>(&self, key: &Identifier<T>) -> Option<&K> {
+        let value: &dyn Any = &**self.values.get(key)?;
+        value.downcast_ref()
+    }
+
+    /// Returns `true` if a value is registered under `key`.
+    pub fn contains_key(&self, key: &Identifier<T>) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Removes the value registered under `key`, returning `true` if there
+    /// was one.
+    pub fn remove(&mut self, key: &Identifier<T>) -> bool {
+        self.values.remove(key).is_some()
+    }
+
+    /// Returns the number of registered values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if nothing is registered.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the registered values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Keyed<T>> {
+        self.values.values().map(|value| &**value as &dyn Keyed<T>)
+    }
+
+    /// Returns the registered values that are a `K`, in no particular order.
+    pub fn iter_as<K: Any>(&self) -> impl Iterator<Item = &K> {
+        self.values.values().filter_map(|value| {
+            let value: &dyn Any = &**value;
+            value.downcast_ref()
+        })
+    }
+}
+
+impl<T: 'static> Default for KeyedRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Debug for KeyedRegistry<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_set().entries(self.values.keys()).finish()
+    }
+}
+
+/// A list of [`Keyed`] values in insertion order, indexed by key.
+///
+/// Each key appears at most once, so [`get`](Self::get) finds a value in
+/// O(1) without a separate map to keep in sync.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, keyed::KeyedVec};
+///
+/// let stone = IdentifierUntyped::parse("game:stone").unwrap();
+/// let dirt = IdentifierUntyped::parse("game:dirt").unwrap();
+/// let mut blocks = KeyedVec::new();
+/// assert!(blocks.push(stone.clone()).is_ok());
+/// assert!(blocks.push(dirt.clone()).is_ok());
+/// assert!(blocks.push(stone.clone()).is_err());
+///
+/// assert_eq!(blocks.index_of(&dirt), Some(1));
+/// assert_eq!(blocks[0], stone);
+/// ```
+pub struct KeyedVec<V, T = ()> {
+    items: Vec<V>,
+    indices: HashMap<Identifier<T>, usize>,
+}
+
+impl<V: Keyed<T>, T> KeyedVec<V, T> {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Appends `value`, or hands it back if its key is already present.
+    pub fn push(&mut self, value: V) -> Result<(), V> {
+        if self.indices.contains_key(value.key()) {
+            return Err(value);
+        }
+        self.indices.insert(value.key().clone(), self.items.len());
+        self.items.push(value);
+        Ok(())
+    }
+
+    /// Returns the value with the given key.
+    pub fn get(&self, key: &Identifier<T>) -> Option<&V> {
+        self.items.get(*self.indices.get(key)?)
+    }
+
+    /// Returns the position of the value with the given key.
+    pub fn index_of(&self, key: &Identifier<T>) -> Option<usize> {
+        self.indices.get(key).copied()
+    }
+
+    /// Returns `true` if a value has the given key.
+    pub fn contains_key(&self, key: &Identifier<T>) -> bool {
+        self.indices.contains_key(key)
+    }
+
+    /// Removes and returns the last value.
+    pub fn pop(&mut self) -> Option<V> {
+        let value = self.items.pop()?;
+        self.indices.remove(value.key());
+        Some(value)
+    }
+
+    /// Removes and returns the value with the given key, shifting the
+    /// values after it down.
+    fn remove(&mut self, key: &Identifier<T>) -> Option<V> {
+        let idx = self.indices.remove(key)?;
+        let value = self.items.remove(idx);
+        for item in &self.items[idx..] {
+            if let Some(idx) = self.indices.get_mut(item.key()) {
+                *idx -= 1;
+            }
+        }
+        Some(value)
+    }
+}
+
+impl<V, T> KeyedVec<V, T> {
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the values in insertion order.
+    pub fn iter(&self) -> std::slice::Iter<'_, V> {
+        self.items.iter()
+    }
+
+    /// Returns the values in insertion order.
+    pub fn as_slice(&self) -> &[V] {
+        &self.items
+    }
+
+    /// Returns the values in insertion order.
+    pub fn into_vec(self) -> Vec<V> {
+        self.items
+    }
+}
+
+impl<V: Keyed<T>, T> Default for KeyedVec<V, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, T> Index<usize> for KeyedVec<V, T> {
+    type Output = V;
+
+    fn index(&self, idx: usize) -> &V {
+        &self.items[idx]
+    }
+}
+
+impl<'a, V, T> IntoIterator for &'a KeyedVec<V, T> {
+    type Item = &'a V;
+    type IntoIter = std::slice::Iter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<V, T> IntoIterator for KeyedVec<V, T> {
+    type Item = V;
+    type IntoIter = std::vec::IntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<V: Debug, T> Debug for KeyedVec<V, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_list().entries(&self.items).finish()
+    }
+}
+
+/// A map of [`Keyed`] values by their key, iterated in insertion order.
+///
+/// Unlike a `HashMap<Identifier<T>, V>`, the key is read from the value, so
+/// the two can't disagree.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, keyed::KeyedMap};
+///
+/// let stone = IdentifierUntyped::parse("game:stone").unwrap();
+/// let mut blocks = KeyedMap::new();
+/// assert!(blocks.insert(stone.clone()).is_ok());
+/// assert!(blocks.contains_key(&stone));
+/// assert_eq!(blocks.remove(&stone), Some(stone));
+/// assert!(blocks.is_empty());
+/// ```
+pub struct KeyedMap<V, T = ()> {
+    items: KeyedVec<V, T>,
+}
+
+impl<V: Keyed<T>, T> KeyedMap<V, T> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            items: KeyedVec::new(),
+        }
+    }
+
+    /// Inserts `value`, or hands it back if its key is already present.
+    pub fn insert(&mut self, value: V) -> Result<(), V> {
+        self.items.push(value)
+    }
+
+    /// Returns the value with the given key.
+    pub fn get(&self, key: &Identifier<T>) -> Option<&V> {
+        self.items.get(key)
+    }
+
+    /// Returns `true` if a value has the given key.
+    pub fn contains_key(&self, key: &Identifier<T>) -> bool {
+        self.items.contains_key(key)
+    }
+
+    /// Removes and returns the value with the given key, keeping the order
+    /// of the rest. Takes O(n) time.
+    pub fn remove(&mut self, key: &Identifier<T>) -> Option<V> {
+        self.items.remove(key)
+    }
+
+    /// Returns the keys in insertion order.
+    pub fn keys(&self) -> impl Iterator<Item = &Identifier<T>> {
+        self.items.iter().map(V::key)
+    }
+}
+
+impl<V, T> KeyedMap<V, T> {
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the values in insertion order.
+    pub fn values(&self) -> std::slice::Iter<'_, V> {
+        self.items.iter()
+    }
+}
+
+impl<V: Keyed<T>, T> Default for KeyedMap<V, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, V, T> IntoIterator for &'a KeyedMap<V, T> {
+    type Item = &'a V;
+    type IntoIter = std::slice::Iter<'a, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values()
+    }
+}
+
+impl<V, T> IntoIterator for KeyedMap<V, T> {
+    type Item = V;
+    type IntoIter = std::vec::IntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<V: Debug, T> Debug for KeyedMap<V, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        Debug::fmt(&self.items, f)
+    }
+}
+
+/// Registry-browsing adaptors for iterators over [`Keyed`] values.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, keyed::KeyedIteratorExt};
+///
+/// let ids: Vec<_> = ["mod:ruby", "game:stone", "game:dirt"]
+///     .into_iter()
+///     .map(|s| IdentifierUntyped::parse(s).unwrap())
+///     .collect();
+///
+/// let sorted: Vec<_> = ids.iter().sorted_by_key().collect();
+/// assert_eq!(sorted[0], "game:dirt");
+/// assert_eq!(ids.iter().group_by_namespace()["game"].len(), 2);
+/// assert!(ids.iter().find_key("mod:ruby").is_some());
+/// ```
+pub trait KeyedIteratorExt<T>: Iterator
+where
+    Self::Item: Keyed<T>,
+{
+    /// Collects the items and sorts them by key.
+    fn sorted_by_key(self) -> std::vec::IntoIter<Self::Item>
+    where
+        Self: Sized,
+    {
+        let mut items: Vec<_> = self.collect();
+        items.sort_by(|a, b| a.key().cmp(b.key()));
+        items.into_iter()
+    }
+
+    /// Collects the items into lists by namespace, keeping their order.
+    fn group_by_namespace(self) -> BTreeMap<String, Vec<Self::Item>>
+    where
+        Self: Sized,
+    {
+        let mut groups: BTreeMap<String, Vec<Self::Item>> = BTreeMap::new();
+        for item in self {
+            match groups.get_mut(item.key().namespace()) {
+                Some(group) => group.push(item),
+                None => {
+                    groups.insert(item.key().namespace_string(), vec![item]);
+                }
+            }
+        }
+        groups
+    }
+
+    /// Returns the first item whose key is the `namespace:value` string
+    /// `key`.
+    fn find_key(mut self, key: &str) -> Option<Self::Item>
+    where
+        Self: Sized,
+    {
+        self.find(|item| item.key().as_str() == key)
+    }
+}
+
+impl<I: Iterator, T> KeyedIteratorExt<T> for I where I::Item: Keyed<T> {}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use super::{Keyed, KeyedIteratorExt, KeyedMap, KeyedRegistry, KeyedVec};
+    use crate::{Identifier, IdentifierUntyped};
+
+    struct Item;
+
+    struct Sword(Identifier<Item>);
+
+    impl Keyed<Item> for Sword {
+        fn key(&self) -> &Identifier<Item> {
+            &self.0
+        }
+    }
+
+    fn keys<T>(values: &[&dyn Keyed<T>]) -> Vec<String> {
+        values.iter().map(|value| value.key().to_string()).collect()
+    }
+
+    #[test]
+    fn typed_and_untyped() {
+        let sword = Sword(Identifier::parse("game:sword").unwrap());
+        let other = Identifier::<Item>::parse("game:bow").unwrap();
+        assert_eq!(keys(&[&sword, &other]), ["game:sword", "game:bow"]);
+
+        let untyped = IdentifierUntyped::parse("game:stone").unwrap();
+        let values: [&dyn Keyed; 1] = [&untyped];
+        assert_eq!(values[0].key(), "game:stone");
+    }
+
+    #[test]
+    fn accessors() {
+        let sword = Sword(Identifier::parse("game:tools/sword").unwrap());
+        let value: &dyn Keyed<Item> = &sword;
+        assert_eq!(value.namespace(), "game");
+        assert_eq!(value.value(), "tools/sword");
+        assert_eq!(value.key_string(), "game:tools/sword");
+    }
+
+    #[test]
+    fn pointers() {
+        fn key_of<K: Keyed<Item>>(value: K) -> String {
+            value.key().to_string()
+        }
+
+        let sword = Sword(Identifier::parse("game:sword").unwrap());
+        assert_eq!(key_of(&sword), "game:sword");
+        let boxed: Box<dyn Keyed<Item>> = Box::new(sword);
+        assert_eq!(key_of(&boxed), "game:sword");
+        assert_eq!(key_of(boxed), "game:sword");
+
+        let bow = Identifier::<Item>::parse("game:bow").unwrap();
+        assert_eq!(key_of(Rc::new(bow.clone())), "game:bow");
+        assert_eq!(key_of(Arc::new(bow)), "game:bow");
+    }
+
+    #[test]
+    fn registry() {
+        let mut registry = KeyedRegistry::new();
+        let sword = Identifier::<Item>::parse("game:sword").unwrap();
+        assert!(registry.register(Sword(sword.clone())).is_ok());
+        let bow = Identifier::<Item>::parse("game:bow").unwrap();
+        assert!(registry.register(bow.clone()).is_ok());
+        assert!(registry.register(sword.clone()).is_err());
+        assert_eq!(registry.len(), 2);
+
+        assert_eq!(registry.get(&sword).unwrap().key(), &sword);
+        assert!(registry.get_as::<Sword>(&sword).is_some());
+        assert!(registry.get_as::<Sword>(&bow).is_none());
+        assert_eq!(registry.get_as::<Identifier<Item>>(&bow), Some(&bow));
+        assert_eq!(registry.iter_as::<Sword>().count(), 1);
+
+        assert!(registry.remove(&sword));
+        assert!(!registry.contains_key(&sword));
+        assert!(!registry.remove(&sword));
+    }
+
+    #[test]
+    fn collections() {
+        let parse = |s| Identifier::<Item>::parse(s).unwrap();
+        let mut vec = KeyedVec::new();
+        for key in ["game:sword", "game:bow", "game:axe"] {
+            assert!(vec.push(Sword(parse(key))).is_ok());
+        }
+        assert!(vec.push(Sword(parse("game:bow"))).is_err());
+        assert_eq!(vec.get(&parse("game:axe")).unwrap().key(), "game:axe");
+        assert_eq!(vec.index_of(&parse("game:bow")), Some(1));
+        assert_eq!(vec.pop().unwrap().key(), "game:axe");
+        assert!(!vec.contains_key(&parse("game:axe")));
+
+        let mut map = KeyedMap::new();
+        for key in ["game:sword", "game:bow", "game:axe", "game:pick"] {
+            assert!(map.insert(parse(key)).is_ok());
+        }
+        assert_eq!(map.remove(&parse("game:bow")), Some(parse("game:bow")));
+        assert_eq!(map.remove(&parse("game:bow")), None);
+        let keys: Vec<_> = map.keys().map(|key| key.to_string()).collect();
+        assert_eq!(keys, ["game:sword", "game:axe", "game:pick"]);
+        assert_eq!(map.get(&parse("game:pick")), Some(&parse("game:pick")));
+        assert!(map.insert(parse("game:bow")).is_ok());
+        assert_eq!(map.len(), 4);
+    }
+
+    #[test]
+    fn iterator_ext() {
+        let swords: Vec<_> = ["mod:sword", "game:sword", "game:axe"]
+            .into_iter()
+            .map(|s| Sword(Identifier::parse(s).unwrap()))
+            .collect();
+
+        let sorted: Vec<_> = swords
+            .iter()
+            .sorted_by_key()
+            .map(Keyed::key_string)
+            .collect();
+        assert_eq!(sorted, ["game:axe", "game:sword", "mod:sword"]);
+
+        let groups = swords.iter().group_by_namespace();
+        let namespaces: Vec<_> = groups.keys().collect();
+        assert_eq!(namespaces, ["game", "mod"]);
+        assert_eq!(groups["game"][1].key(), "game:axe");
+
+        assert!(swords.iter().find_key("game:axe").is_some());
+        assert!(swords.iter().find_key("axe").is_none());
+    }
+}
@@ -5,38 +5,367 @@ use std::{
     hash::{Hash, Hasher},
     marker::PhantomData,
     str::FromStr,
-    sync::OnceLock,
+    sync::{Arc, OnceLock},
 };
 
-use internment::Intern;
+use crate::{
+    intern::Namespace,
+    limits::{LengthLimits, LengthOf, length_limits},
+    options::ParseOptions,
+};
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+#[cfg(feature = "bevy_reflect")]
+mod bevy_reflect;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "bson")]
+pub mod bson;
+pub mod bulk;
+pub mod canonical;
+#[cfg(feature = "clap")]
+pub mod clap;
+pub mod diagnostics;
+#[cfg(any(
+    feature = "arbitrary",
+    feature = "proptest",
+    feature = "quickcheck"
+))]
+mod generate;
+pub mod intern;
+pub mod join;
+#[cfg(feature = "juniper")]
+mod juniper;
+pub mod keyed;
+pub mod limits;
+pub mod namespaced;
+pub mod options;
+pub mod ordering;
+pub mod path;
+pub mod phf;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "protocol")]
+pub mod protocol;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+pub mod reader;
+#[cfg(feature = "redis")]
+pub mod redis;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "rocket")]
+mod rocket;
+#[cfg(feature = "schemars")]
+mod schemars;
+#[cfg(feature = "sea-orm")]
+mod sea_orm;
 #[cfg(feature = "serde")]
-use serde::{Deserialize, Serialize};
+pub mod serde;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+pub mod translation;
+pub mod typed;
+mod uri;
+#[cfg(feature = "url")]
+pub mod url;
+#[cfg(feature = "utoipa")]
+mod utoipa;
+#[cfg(feature = "uuid")]
+mod uuid;
+#[cfg(feature = "winnow")]
+pub mod winnow;
+pub mod wire;
+
+macro_rules! default_namespace {
+    () => {
+        "unspecified"
+    };
+}
 
 /// The default namespace string when none is provided.
-pub const DEFAULT_NAMESPACE: &str = "unspecified";
+pub const DEFAULT_NAMESPACE: &str = default_namespace!();
 
 /// The separator character between the namespace and value.
 pub const DEFAULT_SEPARATOR: char = ':';
 
+/// A regular expression matching the `namespace:value` form accepted by
+/// [`Identifier::parse`].
+pub const IDENTIFIER_PATTERN: &str = r"^(?:[0-9a-z_.-]*:)?[0-9a-z_./-]+$";
+
+/// A human-readable description of [`IDENTIFIER_PATTERN`], as used by the
+/// generated JSON and OpenAPI schemas.
+pub const IDENTIFIER_DESCRIPTION: &str = concat!(
+    "A `namespace:value` identifier. The namespace defaults to `",
+    default_namespace!(),
+    "` when omitted."
+);
+
+pub(crate) const VALUE_CHARS: &str = "0123456789abcdefghijklmnopqrstuvwxyz_-./";
+pub(crate) const NAMESPACE_CHARS: &str =
+    "0123456789abcdefghijklmnopqrstuvwxyz_-.";
+
 static LEGAL_VALUE: OnceLock<HashSet<char>> = OnceLock::new();
 static LEGAL_NS: OnceLock<HashSet<char>> = OnceLock::new();
+static VALUE_TABLE: OnceLock<CharTable> = OnceLock::new();
+static NS_TABLE: OnceLock<CharTable> = OnceLock::new();
 
 /// Returns the set of legal characters for [`Identifier`] values.
 pub fn legal_value_chars() -> &'static HashSet<char> {
-    LEGAL_VALUE.get_or_init(|| {
-        "0123456789abcdefghijklmnopqrstuvwxyz_-./".chars().collect()
-    })
+    LEGAL_VALUE.get_or_init(|| VALUE_CHARS.chars().collect())
 }
 
 /// Returns the set of legal characters for [`Identifier`] namespaces.
 pub fn legal_namespace_chars() -> &'static HashSet<char> {
-    LEGAL_NS.get_or_init(|| {
-        "0123456789abcdefghijklmnopqrstuvwxyz_-.".chars().collect()
-    })
+    LEGAL_NS.get_or_init(|| NAMESPACE_CHARS.chars().collect())
+}
+
+/// Installs a custom set of legal characters for [`Identifier`] values,
+/// replacing `0-9`, `a-z`, `_`, `-`, `.` and `/`.
+///
+/// This only works before the set is first used, i.e. before any
+/// identifier is validated; call it at startup. Otherwise the set is handed
+/// back unchanged. [`IDENTIFIER_PATTERN`] does not reflect custom sets.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{legal_value_chars, set_legal_value_chars};
+///
+/// let mut chars = legal_value_chars().clone();
+/// // Too late: `legal_value_chars` initialized the default set above.
+/// chars.insert('+');
+/// assert!(set_legal_value_chars(chars).is_err());
+/// ```
+pub fn set_legal_value_chars(
+    chars: HashSet<char>,
+) -> Result<(), HashSet<char>> {
+    LEGAL_VALUE.set(chars)
+}
+
+/// Installs a custom set of legal characters for [`Identifier`] namespaces,
+/// replacing `0-9`, `a-z`, `_`, `-` and `.`.
+///
+/// Like [`set_legal_value_chars`], this only works before the set is first
+/// used. The separator should not be included.
+pub fn set_legal_namespace_chars(
+    chars: HashSet<char>,
+) -> Result<(), HashSet<char>> {
+    LEGAL_NS.set(chars)
+}
+
+/// A legal character set prepared for fast lookups: a direct table for the
+/// first 256 code points, falling back to the set itself for the rest.
+#[derive(Debug)]
+pub(crate) struct CharTable {
+    low: [bool; 256],
+    set: &'static HashSet<char>,
+    ascii_only: bool,
+}
+
+impl CharTable {
+    fn new(set: &'static HashSet<char>) -> Self {
+        let mut low = [false; 256];
+        for &ch in set {
+            if let Ok(byte) = u8::try_from(ch) {
+                low[usize::from(byte)] = true;
+            }
+        }
+        let ascii_only = set.iter().all(char::is_ascii);
+        Self {
+            low,
+            set,
+            ascii_only,
+        }
+    }
+
+    /// Returns `true` if `ch` is in the set.
+    #[inline]
+    pub(crate) fn contains(&self, ch: char) -> bool {
+        match u8::try_from(ch) {
+            Ok(byte) => self.low[usize::from(byte)],
+            Err(_) => self.set.contains(&ch),
+        }
+    }
+
+    /// Returns `true` if `bytes` are all ASCII and every one is in the set.
+    ///
+    /// This looks at whole eight-byte chunks at a time without branching per
+    /// byte, so it is much faster than checking each char. A `false` result
+    /// only means the slow path has to decide.
+    #[inline]
+    pub(crate) fn contains_all_ascii(&self, bytes: &[u8]) -> bool {
+        let legal = |byte: u8| byte.is_ascii() & self.low[usize::from(byte)];
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            if !chunk.iter().fold(true, |ok, &byte| ok & legal(byte)) {
+                return false;
+            }
+        }
+        chunks.remainder().iter().all(|&byte| legal(byte))
+    }
+}
+
+/// Returns [`legal_value_chars`] as a lookup table.
+pub(crate) fn value_table() -> &'static CharTable {
+    VALUE_TABLE.get_or_init(|| CharTable::new(legal_value_chars()))
+}
+
+/// Returns [`legal_namespace_chars`] as a lookup table.
+pub(crate) fn namespace_table() -> &'static CharTable {
+    NS_TABLE.get_or_init(|| CharTable::new(legal_namespace_chars()))
+}
+
+/// The set of characters an [`Identifier`] is validated against: always
+/// [`legal_namespace_chars`] and [`legal_value_chars`], plus whatever the
+/// flags allow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct Charset {
+    /// Also allow ASCII uppercase letters.
+    pub(crate) uppercase: bool,
+    /// Also allow non-ASCII alphanumerics.
+    #[cfg(feature = "unicode")]
+    pub(crate) unicode: bool,
+}
+
+impl Charset {
+    /// Only the legal characters.
+    pub(crate) const STRICT: Self = Self {
+        uppercase: false,
+        #[cfg(feature = "unicode")]
+        unicode: false,
+    };
+
+    fn allows_extra(self, ch: char) -> bool {
+        #[cfg(feature = "unicode")]
+        if self.unicode && !ch.is_ascii() && ch.is_alphanumeric() {
+            return true;
+        }
+        self.uppercase && ch.is_ascii_uppercase()
+    }
+
+    /// Returns `true` if these flags may allow a non-ASCII char.
+    fn allows_non_ascii(self) -> bool {
+        #[cfg(feature = "unicode")]
+        if self.unicode {
+            return true;
+        }
+        false
+    }
+}
+
+/// Everything a namespace and value are validated against, looked up once.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Rules {
+    namespace: &'static CharTable,
+    value: &'static CharTable,
+    pub(crate) limits: LengthLimits,
+    charset: Charset,
+}
+
+impl Rules {
+    /// The process-wide charsets and length limits, extended by `charset`.
+    pub(crate) fn global(charset: Charset) -> Self {
+        Self {
+            namespace: namespace_table(),
+            value: value_table(),
+            limits: *length_limits(),
+            charset,
+        }
+    }
+
+    fn allows_in_namespace(&self, ch: char) -> bool {
+        self.namespace.contains(ch) || self.charset.allows_extra(ch)
+    }
+
+    fn allows_in_value(&self, ch: char) -> bool {
+        self.value.contains(ch) || self.charset.allows_extra(ch)
+    }
+
+    /// Rejects `input` at its first non-ASCII byte if no non-ASCII char
+    /// could be legal anywhere in it.
+    ///
+    /// This is one cheap pass over the raw bytes, run before splitting, so
+    /// input that can never be valid is turned away without decoding it.
+    pub(crate) fn check_ascii(&self, input: &str) -> Result<(), ParseError> {
+        if input.is_ascii()
+            || !self.namespace.ascii_only
+            || !self.value.ascii_only
+            || self.charset.allows_non_ascii()
+        {
+            return Ok(());
+        }
+        match input.bytes().position(|b| !b.is_ascii()) {
+            Some(index) => Err(ParseError::NonAscii { index }),
+            None => Ok(()),
+        }
+    }
+
+    /// Validates `namespace` and `value`, stopping at the first part with a
+    /// problem.
+    pub(crate) fn check(
+        &self,
+        namespace: &str,
+        value: &str,
+    ) -> Result<(), ParseError> {
+        if value.is_empty() {
+            return Err(ParseError::EmptyValue);
+        }
+        self.limits.check(namespace, value)?;
+        self.check_namespace(namespace)?;
+        self.check_value(value)
+    }
+
+    // Most input is plain legal ASCII, which the bytewise checks below accept
+    // without decoding a single char. Anything else takes the scalar path,
+    // which also finds the positions to report.
+
+    /// Validates the characters of `namespace`.
+    pub(crate) fn check_namespace(
+        &self,
+        namespace: &str,
+    ) -> Result<(), ParseError> {
+        if !self.namespace.contains_all_ascii(namespace.as_bytes()) {
+            // Collect *all* bad chars in the namespace
+            let bad_ns =
+                illegal_chars(namespace, |ch| self.allows_in_namespace(ch));
+            if !bad_ns.is_empty() {
+                return Err(ParseError::IllegalCharsInNamespace(
+                    namespace.to_owned(),
+                    bad_ns,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validates the characters of `value`.
+    pub(crate) fn check_value(&self, value: &str) -> Result<(), ParseError> {
+        if !self.value.contains_all_ascii(value.as_bytes()) {
+            // Collect *all* bad chars in the value
+            let bad_val = illegal_chars(value, |ch| self.allows_in_value(ch));
+            if !bad_val.is_empty() {
+                return Err(ParseError::IllegalCharsInValue(
+                    value.to_owned(),
+                    bad_val,
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 /// An identifier consisting of a `namespace` and a `value`.
 ///
+/// Before 2.0 these were public fields. They are now read with
+/// [`namespace`](Identifier::namespace) and [`value`](Identifier::value),
+/// and the value is changed with [`set_value`](Identifier::set_value).
+///
 /// # Examples
 ///
 /// ```
@@ -46,8 +375,8 @@ pub fn legal_namespace_chars() -> &'static HashSet<char> {
 /// let loc: IdentifierUntyped = Identifier::from_str("game:item/sword").unwrap();
 /// //       ^^^^^^^^^^^^^^^^^ alias for `Identifier<()>`
 /// assert_eq!(loc.namespace(), "game");
-/// assert_eq!(loc.value, "item/sword");
-/// assert_eq!(loc.to_string(), "game:item/sword");
+/// assert_eq!(loc.value(), "item/sword");
+/// assert_eq!(loc.as_str(), "game:item/sword");
 ///
 /// let fallback: IdentifierUntyped = Identifier::from_str("thing").unwrap();
 /// assert_eq!(fallback.namespace(), "unspecified"); // uses default
@@ -56,47 +385,205 @@ pub fn legal_namespace_chars() -> &'static HashSet<char> {
 ///
 /// # Performance
 ///
-/// Cloning an `Identifier` is cheap: the `namespace` is automatically
-/// [interned][internment], so multiple instances sharing the same namespace
-/// do not duplicate memory or perform allocations (for the namespace portion,
-/// at least).
+/// An `Identifier` is a single pointer, 8 bytes on 64-bit targets, to a
+/// reference-counted `namespace:value` string, so cloning never allocates.
+/// The `namespace` is automatically [interned][internment] alongside it, so
+/// comparing namespaces is a pointer comparison, and
+/// [`namespace`](Identifier::namespace) is a slice of the full form.
+///
+/// Interned namespaces are never freed, unless the `arc-intern` feature is
+/// enabled: then they are reference counted and freed along with the last
+/// identifier using them. With the `no-intern` feature, which takes
+/// precedence, namespaces are not interned at all: each identifier
+/// allocates its own. That skips the interner's locking and keeps memory
+/// from growing in short-lived or single-threaded programs.
+///
+/// Because the full form is stored, [`as_str`] and [`Display`] never
+/// allocate either.
 ///
+/// With the `cached-hash` feature, [`stable_hash64`] is also computed once
+/// at construction, and [`Hash`] feeds just that digest to the hasher. This
+/// makes hashing and unequal comparisons constant time, but FNV-1a is not
+/// keyed, so colliding identifiers are easy to craft: don't enable it if
+/// map keys come from untrusted input.
+///
+/// With the `fast-hash` feature, the namespace is digested with FNV-1a once
+/// at construction, and [`Hash`] feeds that digest and the value's
+/// characters to the hasher, which is faster for long namespaces. The same
+/// caveat about untrusted input applies.
+/// `cached-hash` takes precedence if both are enabled.
+///
+/// [`stable_hash64`]: Identifier::stable_hash64
+///
+/// [`as_str`]: Identifier::as_str
 /// [internment]: https://docs.rs/internment/latest/internment/
-#[derive(Debug)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg_attr(feature = "serde", serde(bound(serialize = "", deserialize = "")))]
-#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(::rkyv::Archive, ::rkyv::Serialize, ::rkyv::Deserialize)
+)]
+#[cfg_attr(
+    feature = "juniper",
+    ::juniper::graphql_scalar(
+        name = "Identifier",
+        description = "A `namespace:value` identifier.",
+        with = crate::juniper,
+        parse_token(String)
+    )
+)]
 pub struct Identifier<T> {
-    pub namespace: Intern<String>,
-    pub value: String,
-    #[cfg_attr(feature = "serde", serde(skip))]
-    type_marker: PhantomData<T>,
+    /// `Arc<Full>` rather than `Arc<str>` keeps this a thin pointer.
+    #[cfg_attr(feature = "rkyv", rkyv(with = crate::rkyv::SharedFull))]
+    full: Arc<Full>,
+    /// `fn() -> T` keeps the marker from affecting `Send`, `Sync` and
+    /// `Unpin`.
+    type_marker: PhantomData<fn() -> T>,
+}
+
+/// The reference-counted part of an [`Identifier`].
+#[derive(Debug, Clone)]
+pub(crate) struct Full {
+    /// The interned namespace, also the start of `string`.
+    pub(crate) namespace: Namespace,
+    /// The namespace's length, so slicing `string` needs no trip through
+    /// the interner.
+    namespace_len: usize,
+    /// `namespace:value`; the value is everything after the separator that
+    /// follows the namespace.
+    pub(crate) string: String,
+    /// [`fnv1a`] of `string`, computed once.
+    #[cfg(feature = "cached-hash")]
+    hash: u64,
+    /// [`fnv1a`] of the namespace, computed once.
+    #[cfg(all(feature = "fast-hash", not(feature = "cached-hash")))]
+    namespace_hash: u64,
 }
 
+impl Full {
+    /// `string` must start with `namespace` and the separator.
+    pub(crate) fn new(namespace: Namespace, string: String) -> Self {
+        Self {
+            #[cfg(all(feature = "fast-hash", not(feature = "cached-hash")))]
+            namespace_hash: fnv1a(&namespace),
+            namespace_len: namespace.len(),
+            namespace,
+            #[cfg(feature = "cached-hash")]
+            hash: fnv1a(&string),
+            string,
+        }
+    }
+
+    /// The length of the namespace and the separator after it.
+    fn value_start(&self) -> usize {
+        self.namespace_len + DEFAULT_SEPARATOR.len_utf8()
+    }
+}
+
+impl PartialEq for Full {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "cached-hash")]
+        if self.hash != other.hash {
+            return false;
+        }
+        self.namespace == other.namespace && self.string == other.string
+    }
+}
+
+impl Eq for Full {}
+
 pub type IdentifierUntyped = Identifier<()>;
 
 impl<T> Clone for Identifier<T> {
     fn clone(&self) -> Self {
         Identifier {
-            namespace: self.namespace,
-            value: self.value.clone(),
+            full: Arc::clone(&self.full),
             type_marker: PhantomData,
         }
     }
 }
 
+/// Prints the marker type without module paths and the full form, like
+/// `Identifier<Block>("game:stone")`.
+impl<T> Debug for Identifier<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let name = format!(
+            "Identifier<{}>",
+            short_type_name(std::any::type_name::<T>())
+        );
+        f.debug_tuple(&name).field(&self.as_str()).finish()
+    }
+}
+
+/// Strips the module paths from every type in `name`, so
+/// `alloc::vec::Vec<my_crate::Block>` becomes `Vec<Block>`.
+fn short_type_name(name: &str) -> String {
+    let mut short = String::with_capacity(name.len());
+    let mut segment_start = 0;
+    let mut rest = name;
+    while let Some(ch) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("::") {
+            short.truncate(segment_start);
+            rest = after;
+            continue;
+        }
+        if !(ch.is_alphanumeric() || ch == '_') {
+            segment_start = short.len() + ch.len_utf8();
+        }
+        short.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+    short
+}
+
 impl<T> PartialEq for Identifier<T> {
     fn eq(&self, other: &Self) -> bool {
-        self.namespace == other.namespace && self.value == other.value
+        self.full == other.full
     }
 }
 
 impl<T> Eq for Identifier<T> {}
 
+/// Compares against the full `namespace:value` form, so `"stone"` is not
+/// equal to `unspecified:stone`.
+impl<T> PartialEq<str> for Identifier<T> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<T> PartialEq<&str> for Identifier<T> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<T> PartialEq<Identifier<T>> for str {
+    fn eq(&self, other: &Identifier<T>) -> bool {
+        other == self
+    }
+}
+
+impl<T> PartialEq<Identifier<T>> for &str {
+    fn eq(&self, other: &Identifier<T>) -> bool {
+        other == *self
+    }
+}
+
 impl<T> Hash for Identifier<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.namespace.hash(state);
-        self.value.hash(state);
+        // Always derived from the characters, never the interned pointer,
+        // so equal identifiers from different interners hash the same.
+        #[cfg(feature = "cached-hash")]
+        state.write_u64(self.full.hash);
+        #[cfg(all(feature = "fast-hash", not(feature = "cached-hash")))]
+        {
+            state.write_u64(self.full.namespace_hash);
+            self.value().hash(state);
+        }
+        #[cfg(not(any(feature = "fast-hash", feature = "cached-hash")))]
+        {
+            self.namespace().hash(state);
+            self.value().hash(state);
+        }
     }
 }
 
@@ -108,8 +595,8 @@ impl<T> PartialOrd for Identifier<T> {
 
 impl<T> Ord for Identifier<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.namespace.cmp(&other.namespace) {
-            Ordering::Equal => self.value.cmp(&other.value),
+        match self.namespace().cmp(other.namespace()) {
+            Ordering::Equal => self.value().cmp(other.value()),
             non_eq => non_eq,
         }
     }
@@ -118,76 +605,464 @@ impl<T> Ord for Identifier<T> {
 impl<T> Identifier<T> {
     /// Returns the namespace as a string slice.
     pub fn namespace(&self) -> &str {
-        self.namespace.as_str()
+        &self.full.string[..self.full.namespace_len]
     }
 
     /// Returns the namespace as a String.
     pub fn namespace_string(&self) -> String {
-        (*self.namespace).clone()
+        self.namespace().to_owned()
+    }
+
+    /// Returns the value as a string slice.
+    pub fn value(&self) -> &str {
+        &self.full.string[self.full.value_start()..]
+    }
+
+    /// Returns the full `namespace:value` form, without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("stone").unwrap();
+    /// assert_eq!(id.as_str(), "unspecified:stone");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        self.full.string.as_str()
+    }
+
+    /// Returns the namespace and the value.
+    pub fn as_tuple(&self) -> (&str, &str) {
+        (self.namespace(), self.value())
+    }
+
+    /// Consumes this identifier, returning the namespace and the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:stone").unwrap();
+    /// assert_eq!(id.as_tuple(), ("game", "stone"));
+    /// let (namespace, value) = id.into_parts();
+    /// assert_eq!((namespace.as_str(), value.as_str()), ("game", "stone"));
+    /// ```
+    pub fn into_parts(self) -> (String, String) {
+        (self.namespace_string(), self.into_value())
+    }
+
+    /// Consumes this identifier, returning the value.
+    ///
+    /// This reuses the identifier's own allocation unless it is shared
+    /// with a clone.
+    pub fn into_value(self) -> String {
+        let full = Arc::unwrap_or_clone(self.full);
+        let start = full.value_start();
+        let mut value = full.string;
+        value.drain(..start);
+        value
     }
 
     pub fn new<S: Into<String>>(
         namespace: S,
         value: S,
     ) -> Result<Self, ParseError> {
-        let namespace = namespace.into();
-        let value = value.into();
-
-        if value.is_empty() {
-            return Err(ParseError::EmptyValue);
-        }
+        Self::new_in(namespace.into(), value.into(), Charset::STRICT)
+    }
 
-        // Collect *all* bad chars in the namespace
-        let bad_ns: Vec<(usize, char)> = namespace
-            .char_indices()
-            .filter(|&(_, ch)| !legal_namespace_chars().contains(&ch))
-            .collect();
-        if !bad_ns.is_empty() {
-            return Err(ParseError::IllegalCharsInNamespace(namespace, bad_ns));
-        }
+    /// Builds an [`Identifier`] in [`DEFAULT_NAMESPACE`] from a value.
+    ///
+    /// Unlike [`Identifier::parse`], the whole input is the value, so a
+    /// separator in it is an error rather than a namespace. To build in
+    /// some other fixed namespace, see [`Identifier::in_namespace_of`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::with_default_namespace("stone").unwrap();
+    /// assert_eq!(id.to_string(), "unspecified:stone");
+    /// assert!(IdentifierUntyped::with_default_namespace("game:stone").is_err());
+    /// ```
+    pub fn with_default_namespace<S: Into<String>>(
+        value: S,
+    ) -> Result<Self, ParseError> {
+        Self::new_in(
+            DEFAULT_NAMESPACE.to_owned(),
+            value.into(),
+            Charset::STRICT,
+        )
+    }
 
-        // Collect *all* bad chars in the value
-        let bad_val: Vec<(usize, char)> = value
-            .char_indices()
-            .filter(|&(_, ch)| !legal_value_chars().contains(&ch))
-            .collect();
-        if !bad_val.is_empty() {
-            return Err(ParseError::IllegalCharsInValue(value, bad_val));
-        }
+    /// Validates `namespace` and `value` against `charset` and builds an
+    /// [`Identifier`] from them.
+    pub(crate) fn new_in(
+        namespace: String,
+        value: String,
+        charset: Charset,
+    ) -> Result<Self, ParseError> {
+        Rules::global(charset).check(&namespace, &value)?;
+        Ok(Self::new_unchecked(namespace, value))
+    }
 
+    /// Builds an [`Identifier`] from parts that are already known to be
+    /// legal, defaulting an empty namespace.
+    pub(crate) fn new_unchecked(namespace: String, value: String) -> Self {
         let ns = if namespace.is_empty() {
-            DEFAULT_NAMESPACE.to_string()
+            DEFAULT_NAMESPACE
         } else {
-            namespace
+            &namespace
         };
+        Self::from_parts(Namespace::new(ns), &value)
+    }
+
+    /// Builds an [`Identifier`] from parts that the macros or
+    /// `namespacedkey_codegen` validated at build time. Not part of the
+    /// public API.
+    #[doc(hidden)]
+    pub fn __from_validated_parts(namespace: &str, value: &str) -> Self {
+        Self::from_parts(Namespace::new(namespace), value)
+    }
 
-        Ok(Identifier {
-            namespace: Intern::new(ns),
-            value,
+    /// Builds an [`Identifier`] from an already interned namespace and a
+    /// legal value.
+    pub(crate) fn from_parts(namespace: Namespace, value: &str) -> Self {
+        let mut full = String::with_capacity(
+            namespace.len() + DEFAULT_SEPARATOR.len_utf8() + value.len(),
+        );
+        full.push_str(&namespace);
+        full.push(DEFAULT_SEPARATOR);
+        full.push_str(value);
+        Identifier {
+            full: Arc::new(Full::new(namespace, full)),
             type_marker: PhantomData,
-        })
+        }
     }
 
     /// Parses a string into an [`Identifier`], defaulting the namespace if omitted.
     pub fn parse<S: Into<String>>(s: S) -> Result<Self, ParseError> {
-        let s = s.into();
-        let mut parts = s.splitn(2, DEFAULT_SEPARATOR);
-        let before = parts.next().unwrap_or("");
-        let after = parts.next().unwrap_or(before);
-        let (namespace, value) = if before == after {
-            ("", before)
+        Self::parse_str(&s.into())
+    }
+
+    /// Parses a borrowed string without first copying the whole input.
+    pub(crate) fn parse_str(s: &str) -> Result<Self, ParseError> {
+        let rules = Rules::global(Charset::STRICT);
+        rules.check_ascii(s)?;
+        let (namespace, value) =
+            s.split_once(DEFAULT_SEPARATOR).unwrap_or(("", s));
+        rules.check(namespace, value)?;
+        Ok(Self::new_unchecked(namespace.to_owned(), value.to_owned()))
+    }
+
+    /// Parses raw bytes, e.g. from a network buffer, like
+    /// [`Identifier::parse`].
+    ///
+    /// Legal identifiers are ASCII, so the usual case checks the charset and
+    /// UTF-8 validity in the same pass. Anything else is checked as UTF-8
+    /// first, failing with [`ParseError::InvalidUtf8`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::{IdentifierUntyped, ParseError};
+    ///
+    /// let id = IdentifierUntyped::parse_bytes(b"game:stone").unwrap();
+    /// assert_eq!(id.to_string(), "game:stone");
+    /// assert!(matches!(
+    ///     IdentifierUntyped::parse_bytes(b"game:\xff"),
+    ///     Err(ParseError::InvalidUtf8(_))
+    /// ));
+    /// ```
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+        let separator = DEFAULT_SEPARATOR as u8;
+        let (namespace, value) =
+            match bytes.iter().position(|&b| b == separator) {
+                Some(idx) => (&bytes[..idx], &bytes[idx + 1..]),
+                None => (&[][..], bytes),
+            };
+
+        if !value.is_empty()
+            && namespace_table().contains_all_ascii(namespace)
+            && value_table().contains_all_ascii(value)
+        {
+            // Every byte is ASCII, so every byte is a char.
+            let ascii =
+                |part: &[u8]| part.iter().map(|&b| char::from(b)).collect();
+            let namespace: String = ascii(namespace);
+            let value: String = ascii(value);
+            length_limits().check(&namespace, &value)?;
+            return Ok(Self::new_unchecked(namespace, value));
+        }
+
+        let s = std::str::from_utf8(bytes).map_err(ParseError::InvalidUtf8)?;
+        Self::parse_str(s)
+    }
+
+    /// Parses a string like [`Identifier::parse`] after lowercasing any ASCII
+    /// uppercase letters. The returned flag is `true` if anything was
+    /// lowercased.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let (id, folded) =
+    ///     IdentifierUntyped::parse_folded("Game:IronSword").unwrap();
+    /// assert_eq!(id.to_string(), "game:ironsword");
+    /// assert!(folded);
+    /// ```
+    pub fn parse_folded(s: &str) -> Result<(Self, bool), ParseError> {
+        if s.bytes().any(|b| b.is_ascii_uppercase()) {
+            Ok((Self::parse_str(&s.to_ascii_lowercase())?, true))
         } else {
-            (before, after)
-        };
+            Ok((Self::parse_str(s)?, false))
+        }
+    }
+
+    /// Parses a string like [`Identifier::parse`], but uses
+    /// `default_namespace` instead of [`DEFAULT_NAMESPACE`] when the
+    /// namespace is missing or empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse_with_default("stone", "game").unwrap();
+    /// assert_eq!(id.to_string(), "game:stone");
+    /// let id = IdentifierUntyped::parse_with_default("other:stone", "game")
+    ///     .unwrap();
+    /// assert_eq!(id.to_string(), "other:stone");
+    /// ```
+    pub fn parse_with_default(
+        s: &str,
+        default_namespace: &str,
+    ) -> Result<Self, ParseError> {
+        ParseOptions::new()
+            .default_namespace(default_namespace)
+            .parse(s)
+    }
+
+    /// Parses a string like [`Identifier::parse`], but fails with
+    /// [`ParseError::MissingNamespace`] instead of defaulting the namespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::{IdentifierUntyped, ParseError};
+    ///
+    /// assert!(IdentifierUntyped::parse_strict("game:stone").is_ok());
+    /// assert!(matches!(
+    ///     IdentifierUntyped::parse_strict("stone"),
+    ///     Err(ParseError::MissingNamespace)
+    /// ));
+    /// ```
+    pub fn parse_strict(s: &str) -> Result<Self, ParseError> {
+        ParseOptions::new().require_namespace(true).parse(s)
+    }
+
+    /// Checks whether `s` would parse, without building an [`Identifier`].
+    ///
+    /// Unlike [`Identifier::parse`], which stops at the first part with a
+    /// problem, the returned report lists every problem in the input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// assert!(IdentifierUntyped::validate("game:stone").is_ok());
+    /// let report = IdentifierUntyped::validate("Game:").unwrap_err();
+    /// assert_eq!(report.problems().len(), 2);
+    /// ```
+    pub fn validate(s: &str) -> Result<(), ValidationReport> {
+        let (namespace, value) =
+            s.split_once(DEFAULT_SEPARATOR).unwrap_or(("", s));
+
+        let mut problems = Vec::new();
+        let rules = Rules::global(Charset::STRICT);
+        let bad_ns =
+            illegal_chars(namespace, |ch| rules.allows_in_namespace(ch));
+        if !bad_ns.is_empty() {
+            problems.push(ParseError::IllegalCharsInNamespace(
+                namespace.to_owned(),
+                bad_ns,
+            ));
+        }
+        if value.is_empty() {
+            problems.push(ParseError::EmptyValue);
+        }
+        if let Err(err) = rules.limits.check(namespace, value) {
+            problems.push(err);
+        }
+        let bad_val = illegal_chars(value, |ch| rules.allows_in_value(ch));
+        if !bad_val.is_empty() {
+            problems.push(ParseError::IllegalCharsInValue(
+                value.to_owned(),
+                bad_val,
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ValidationReport { problems })
+        }
+    }
+
+    /// Parses a string like [`Identifier::parse`], but repairs illegal
+    /// characters instead of rejecting them.
+    ///
+    /// ASCII uppercase letters are folded to lowercase and every other
+    /// illegal character is replaced with `_`. The returned flag is `true`
+    /// if anything was changed. An empty value is still an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let (id, changed) =
+    ///     IdentifierUntyped::parse_lossy("Game:Iron Sword").unwrap();
+    /// assert_eq!(id.to_string(), "game:iron_sword");
+    /// assert!(changed);
+    /// ```
+    pub fn parse_lossy(s: &str) -> Result<(Self, bool), ParseError> {
+        fn repair(part: &str, legal: &CharTable, changed: &mut bool) -> String {
+            part.chars()
+                .map(|ch| {
+                    if legal.contains(ch) {
+                        return ch;
+                    }
+                    *changed = true;
+                    let lower = ch.to_ascii_lowercase();
+                    if legal.contains(lower) { lower } else { '_' }
+                })
+                .collect()
+        }
+
+        let (namespace, value) =
+            s.split_once(DEFAULT_SEPARATOR).unwrap_or(("", s));
+        if value.is_empty() {
+            return Err(ParseError::EmptyValue);
+        }
+        let mut changed = false;
+        let namespace = repair(namespace, namespace_table(), &mut changed);
+        let value = repair(value, value_table(), &mut changed);
+        // Repairs can shrink multi-byte chars, so check what is kept
+        length_limits().check(&namespace, &value)?;
+        Ok((Self::new_unchecked(namespace, value), changed))
+    }
+
+    /// Turns arbitrary display text into a valid value in `namespace`.
+    ///
+    /// ASCII letters are lowercased and digits kept. Runs of whitespace and
+    /// ASCII punctuation become a single `_`, with none at either end, and
+    /// every other character is dropped. Fails if `namespace` is invalid or
+    /// nothing is left of `text`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::slugify("game", "My Cool Item!").unwrap();
+    /// assert_eq!(id.to_string(), "game:my_cool_item");
+    /// ```
+    pub fn slugify(namespace: &str, text: &str) -> Result<Self, ParseError> {
+        let mut value = String::with_capacity(text.len());
+        let mut pending_separator = false;
+        for ch in text.chars() {
+            if ch.is_ascii_alphanumeric() {
+                if pending_separator && !value.is_empty() {
+                    value.push('_');
+                }
+                pending_separator = false;
+                value.push(ch.to_ascii_lowercase());
+            } else if ch.is_whitespace() || ch.is_ascii_punctuation() {
+                pending_separator = true;
+            }
+        }
+        Self::new(namespace.to_owned(), value)
+    }
+
+    /// Returns a copy of this identifier with its value replaced by `value`,
+    /// which is validated like in [`Identifier::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let lamp = IdentifierUntyped::parse("game:lamp").unwrap();
+    /// assert_eq!(lamp.with_value("lamp_on").unwrap(), "game:lamp_on");
+    /// assert_eq!(lamp.with_namespace("mod").unwrap(), "mod:lamp");
+    /// assert!(lamp.with_value("Lamp").is_err());
+    /// ```
+    pub fn with_value(&self, value: &str) -> Result<Self, ParseError> {
+        self.check_new_value(value)?;
+        Ok(Self::from_parts(self.full.namespace.clone(), value))
+    }
+
+    /// Returns a copy of this identifier with its namespace replaced by
+    /// `namespace`, which is validated like in [`Identifier::new`].
+    pub fn with_namespace(&self, namespace: &str) -> Result<Self, ParseError> {
+        Self::new(namespace, self.value())
+    }
+
+    /// Returns a copy of this identifier with `prefix` prepended to its
+    /// value, validating the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let lamp = IdentifierUntyped::parse("game:lamp").unwrap();
+    /// assert_eq!(lamp.with_value_prefix("block/").unwrap(), "game:block/lamp");
+    /// assert_eq!(lamp.with_value_suffix("_on").unwrap(), "game:lamp_on");
+    /// ```
+    pub fn with_value_prefix(&self, prefix: &str) -> Result<Self, ParseError> {
+        self.with_value(&format!("{prefix}{}", self.value()))
+    }
 
-        Self::new(namespace, value)
+    /// Returns a copy of this identifier with `suffix` appended to its
+    /// value, validating the result.
+    pub fn with_value_suffix(&self, suffix: &str) -> Result<Self, ParseError> {
+        self.with_value(&format!("{}{suffix}", self.value()))
+    }
+
+    /// Replaces the value with `value`, which is validated like in
+    /// [`Identifier::new`]. On error, this identifier is left unchanged.
+    pub fn set_value(&mut self, value: &str) -> Result<(), ParseError> {
+        self.check_new_value(value)?;
+        let full = Arc::make_mut(&mut self.full);
+        full.string.truncate(full.value_start());
+        full.string.push_str(value);
+        *full =
+            Full::new(full.namespace.clone(), std::mem::take(&mut full.string));
+        Ok(())
+    }
+
+    /// Validates `value` as a replacement for the current value. The
+    /// namespace is already valid, so only the value and limits are checked.
+    fn check_new_value(&self, value: &str) -> Result<(), ParseError> {
+        if value.is_empty() {
+            return Err(ParseError::EmptyValue);
+        }
+        let rules = Rules::global(Charset::STRICT);
+        rules.limits.check(self.namespace(), value)?;
+        rules.check_value(value)
     }
 
     /// Change the phantom type to `U`.
     pub fn cast<U>(self) -> Identifier<U> {
         Identifier {
-            namespace: self.namespace,
-            value: self.value,
+            full: self.full,
             type_marker: PhantomData,
         }
     }
@@ -196,11 +1071,123 @@ impl<T> Identifier<T> {
     pub fn erase(self) -> Identifier<()> {
         self.cast::<()>()
     }
+
+    /// Returns `true` if `other` has the same namespace and value, whatever
+    /// its type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::Identifier;
+    ///
+    /// struct Block;
+    /// struct Item;
+    ///
+    /// let block = Identifier::<Block>::parse("game:stone").unwrap();
+    /// let item = Identifier::<Item>::parse("game:stone").unwrap();
+    /// assert!(block.erased_eq(&item));
+    /// ```
+    pub fn erased_eq<U>(&self, other: &Identifier<U>) -> bool {
+        self.full == other.full
+    }
+
+    /// Returns `true` if `other` has the same value, whatever its namespace
+    /// and type.
+    pub fn eq_ignore_namespace<U>(&self, other: &Identifier<U>) -> bool {
+        self.value() == other.value()
+    }
+
+    /// Returns `true` if the value is `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:stone").unwrap();
+    /// assert!(id.value_eq("stone"));
+    /// assert!(id.in_namespace("game"));
+    /// assert!(!id.in_namespace("gam"));
+    /// ```
+    pub fn value_eq(&self, value: &str) -> bool {
+        self.value() == value
+    }
+
+    /// Returns `true` if the namespace is `namespace`.
+    pub fn in_namespace(&self, namespace: &str) -> bool {
+        self.namespace() == namespace
+    }
+
+    /// Returns a 64-bit digest of this identifier that is stable across
+    /// builds, platforms and versions of this crate, for use on the wire.
+    ///
+    /// The digest is 64-bit [FNV-1a] over the UTF-8 bytes of the canonical
+    /// `namespace:value` form. This algorithm will never change; unlike
+    /// [`Hash`], it does not depend on the hasher or the compiler.
+    ///
+    /// [FNV-1a]: http://www.isthe.com/chongo/tech/comp/fnv/
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:stone").unwrap();
+    /// assert_eq!(id.stable_hash64(), 0x4d30_a910_1063_2a6c);
+    /// ```
+    pub fn stable_hash64(&self) -> u64 {
+        #[cfg(feature = "cached-hash")]
+        return self.full.hash;
+        #[cfg(not(feature = "cached-hash"))]
+        fnv1a(self.as_str())
+    }
+}
+
+/// 64-bit FNV-1a over the UTF-8 bytes of `s`.
+pub(crate) fn fnv1a(s: &str) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    s.bytes().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Returns every character of `part` that is not `legal`, with its byte
+/// index.
+fn illegal_chars(
+    part: &str,
+    legal: impl Fn(char) -> bool,
+) -> Vec<(usize, char)> {
+    part.char_indices().filter(|&(_, ch)| !legal(ch)).collect()
 }
 
+/// Honours width, fill, alignment and precision, so `{:>24}` pads. The
+/// alternate form `{:#}` leaves out [`DEFAULT_NAMESPACE`], printing just
+/// the value for identifiers in it.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::IdentifierUntyped;
+///
+/// let id = IdentifierUntyped::parse("stone").unwrap();
+/// assert_eq!(format!("{id:#}"), "stone");
+/// let id = IdentifierUntyped::parse("other:stone").unwrap();
+/// assert_eq!(format!("{id:#}"), "other:stone");
+/// ```
 impl<T> Display for Identifier<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
-        write!(f, "{}{}{}", self.namespace, DEFAULT_SEPARATOR, self.value)
+        if f.alternate() && self.namespace() == DEFAULT_NAMESPACE {
+            return f.pad(self.value());
+        }
+        f.pad(self.as_str())
+    }
+}
+
+impl<T> AsRef<str> for Identifier<T> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
     }
 }
 
@@ -210,6 +1197,52 @@ pub enum ParseError {
     /// No value after the separator.
     EmptyValue,
 
+    /// No namespace before the separator, or no separator at all, in a
+    /// strict parse.
+    MissingNamespace,
+
+    /// The namespace is not the one required by the marker type, from
+    /// [`Identifier::parse_typed`].
+    WrongNamespace {
+        /// The marker type's [`CATEGORY`](typed::IdentifierType::CATEGORY).
+        category: &'static str,
+        /// The required namespace.
+        expected: &'static str,
+        /// The namespace found instead.
+        found: String,
+    },
+
+    /// The value starts with `/`, in a structural parse.
+    LeadingSlash(String),
+
+    /// The value ends with `/`, in a structural parse.
+    TrailingSlash(String),
+
+    /// The value has two `/` in a row, the second at this byte index, in a
+    /// structural parse.
+    EmptySegment(String, usize),
+
+    /// The input is not valid UTF-8.
+    InvalidUtf8(std::str::Utf8Error),
+
+    /// A part is longer than the installed
+    /// [length limits](limits::set_length_limits) allow.
+    TooLong {
+        /// The part that is too long.
+        of: LengthOf,
+        /// The maximum length, in bytes.
+        limit: usize,
+        /// The actual length, in bytes.
+        len: usize,
+    },
+
+    /// The input has a non-ASCII byte at this index, and the active
+    /// charsets are ASCII-only.
+    NonAscii {
+        /// The byte index of the first non-ASCII byte in the input.
+        index: usize,
+    },
+
     /// One or more illegal characters in the namespace.
     IllegalCharsInNamespace(String, Vec<(usize, char)>),
 
@@ -223,6 +1256,38 @@ impl Display for ParseError {
             ParseError::EmptyValue => {
                 write!(f, "empty value")
             }
+            ParseError::MissingNamespace => {
+                write!(f, "missing namespace")
+            }
+            ParseError::WrongNamespace {
+                category,
+                expected,
+                found,
+            } => {
+                write!(
+                    f,
+                    "expected a {category} in namespace {expected:?}, found \
+                     namespace {found:?}"
+                )
+            }
+            ParseError::LeadingSlash(val) => {
+                write!(f, "value {val:?} starts with `/`")
+            }
+            ParseError::TrailingSlash(val) => {
+                write!(f, "value {val:?} ends with `/`")
+            }
+            ParseError::EmptySegment(val, idx) => {
+                write!(f, "empty path segment in value {val:?} at {idx}")
+            }
+            ParseError::InvalidUtf8(err) => {
+                write!(f, "invalid UTF-8: {err}")
+            }
+            ParseError::NonAscii { index } => {
+                write!(f, "non-ASCII byte at index {index}")
+            }
+            ParseError::TooLong { of, limit, len } => {
+                write!(f, "{of} is {len} bytes long, the maximum is {limit}")
+            }
             ParseError::IllegalCharsInNamespace(ns, bad) => {
                 write!(f, "illegal character(s) in namespace {ns:?}:")?;
                 for (idx, ch) in bad {
@@ -241,6 +1306,120 @@ impl Display for ParseError {
     }
 }
 
+impl ParseError {
+    /// Renders `input` above a line of `^` markers pointing at the offending
+    /// characters, for terminal output.
+    ///
+    /// `input` must be the full string that failed to parse. With the
+    /// `unicode` feature, markers line up under wide characters such as CJK
+    /// too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let err = IdentifierUntyped::parse("game:ba g!d").unwrap_err();
+    /// assert_eq!(err.underline("game:ba g!d"), "game:ba g!d\n       ^ ^");
+    /// ```
+    pub fn underline(&self, input: &str) -> String {
+        let (offset, bad) = match self {
+            ParseError::EmptyValue => {
+                let width = columns(input);
+                return format!("{input}\n{}^", " ".repeat(width));
+            }
+            ParseError::MissingNamespace => return format!("{input}\n^"),
+            ParseError::WrongNamespace { .. } => return caret_at(input, 0),
+            ParseError::TooLong { of, limit, len } => {
+                // Point at the first byte past the limit.
+                let start = match of {
+                    LengthOf::Value => input.len().saturating_sub(*len),
+                    LengthOf::Namespace | LengthOf::Identifier => 0,
+                };
+                return caret_at(input, start + limit);
+            }
+            ParseError::InvalidUtf8(err) => {
+                return caret_at(input, err.valid_up_to());
+            }
+            ParseError::NonAscii { index } => {
+                return caret_at(input, *index);
+            }
+            ParseError::LeadingSlash(val) => {
+                return caret_at(input, input.len().saturating_sub(val.len()));
+            }
+            ParseError::TrailingSlash(_) => {
+                return caret_at(input, input.len().saturating_sub(1));
+            }
+            ParseError::EmptySegment(val, idx) => {
+                let start = input.len().saturating_sub(val.len());
+                return caret_at(input, start + idx);
+            }
+            ParseError::IllegalCharsInNamespace(_, bad) => (0, bad),
+            ParseError::IllegalCharsInValue(val, bad) => {
+                (input.len().saturating_sub(val.len()), bad)
+            }
+        };
+
+        let mut marks = String::new();
+        for (idx, _) in bad {
+            let column = input.get(..offset + idx).map_or(0, columns);
+            let padding = column.saturating_sub(marks.len());
+            marks.push_str(&" ".repeat(padding));
+            marks.push('^');
+        }
+        format!("{input}\n{marks}")
+    }
+}
+
+/// Renders `input` above a single `^` under the character at byte index
+/// `idx`.
+fn caret_at(input: &str, idx: usize) -> String {
+    let column = input.get(..idx).map_or(0, columns);
+    format!("{input}\n{}^", " ".repeat(column))
+}
+
+/// Returns how many terminal columns `s` takes up.
+fn columns(s: &str) -> usize {
+    #[cfg(feature = "unicode")]
+    {
+        unicode_width::UnicodeWidthStr::width(s)
+    }
+    #[cfg(not(feature = "unicode"))]
+    {
+        s.chars().count()
+    }
+}
+
+/// Every problem found by [`Identifier::validate`], in input order.
+#[derive(Debug, thiserror::Error)]
+pub struct ValidationReport {
+    problems: Vec<ParseError>,
+}
+
+impl ValidationReport {
+    /// Returns the problems, each as the [`ParseError`] it would cause.
+    pub fn problems(&self) -> &[ParseError] {
+        &self.problems
+    }
+
+    /// Consumes the report, returning the problems.
+    pub fn into_problems(self) -> Vec<ParseError> {
+        self.problems
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for (i, problem) in self.problems.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            Display::fmt(problem, f)?;
+        }
+        Ok(())
+    }
+}
+
 impl<T> TryFrom<String> for Identifier<T> {
     type Error = ParseError;
     fn try_from(s: String) -> Result<Self, Self::Error> {
@@ -257,34 +1436,182 @@ impl<T> From<Identifier<T>> for String {
 impl<T> FromStr for Identifier<T> {
     type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Identifier::parse(s.to_owned())
+        Identifier::parse_str(s)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{DEFAULT_NAMESPACE, Identifier, ParseError};
+    use crate::{limits::LengthLimits, options::ParseOptions};
     use std::str::FromStr;
 
     #[test]
     fn parse_valid_full() {
         let rl = Identifier::<()>::from_str("foo:bar_baz").unwrap();
-        assert_eq!(rl.namespace.as_ref(), "foo");
-        assert_eq!(rl.value, "bar_baz");
+        assert_eq!(rl.namespace(), "foo");
+        assert_eq!(rl.value(), "bar_baz");
     }
 
     #[test]
     fn parse_valid_default_ns() {
         let rl = Identifier::<()>::from_str(":stone").unwrap();
-        assert_eq!(rl.namespace.as_ref(), DEFAULT_NAMESPACE);
-        assert_eq!(rl.value, "stone");
+        assert_eq!(rl.namespace(), DEFAULT_NAMESPACE);
+        assert_eq!(rl.value(), "stone");
     }
 
     #[test]
     fn parse_missing_separator_defaults() {
         let rl = Identifier::<()>::from_str("no_sep").unwrap();
-        assert_eq!(rl.namespace.as_ref(), DEFAULT_NAMESPACE);
-        assert_eq!(rl.value, "no_sep");
+        assert_eq!(rl.namespace(), DEFAULT_NAMESPACE);
+        assert_eq!(rl.value(), "no_sep");
+    }
+
+    #[test]
+    fn parse_namespace_equal_to_value() {
+        let rl = Identifier::<()>::from_str("stone:stone").unwrap();
+        assert_eq!(rl.namespace(), "stone");
+        assert_eq!(rl.value(), "stone");
+    }
+
+    #[test]
+    fn as_str_matches_display() {
+        for input in ["game:item/sword", "stone", ":stone"] {
+            let id = Identifier::<()>::parse(input).unwrap();
+            assert_eq!(id.as_str(), id.to_string());
+            assert_eq!(id.as_ref() as &str, id.as_str());
+        }
+        let id = Identifier::<()>::parse("game:a").unwrap();
+        let cast = id.canonicalize().unwrap().cast::<u8>();
+        assert_eq!(cast.as_str(), "game:a");
+        assert_eq!(cast.value(), "a");
+    }
+
+    #[test]
+    fn eq_str() {
+        let id = Identifier::<()>::parse("game:stone").unwrap();
+        assert_eq!(id, "game:stone");
+        assert_eq!("game:stone", id);
+        let owned = String::from("game:stone");
+        assert!(id == *owned);
+        assert!(*owned == id);
+        assert_ne!(id, "stone");
+        assert_ne!(Identifier::<()>::parse("stone").unwrap(), "stone");
+    }
+
+    #[test]
+    fn comparison_helpers() {
+        let stone = Identifier::<()>::parse("game:stone").unwrap();
+        let other = Identifier::<u8>::parse("other:stone").unwrap();
+        assert!(stone.eq_ignore_namespace(&other));
+        assert!(!stone.erased_eq(&other));
+        assert!(!stone.value_eq("ston"));
+        let id = Identifier::<()>::parse("stone").unwrap();
+        assert!(id.in_namespace(DEFAULT_NAMESPACE));
+    }
+
+    #[test]
+    fn into_parts() {
+        let id = Identifier::<()>::parse("stone").unwrap();
+        assert_eq!(id.as_tuple(), (DEFAULT_NAMESPACE, "stone"));
+        assert_eq!(
+            id.clone().into_parts(),
+            (DEFAULT_NAMESPACE.to_owned(), "stone".to_owned())
+        );
+        assert_eq!(id.into_value(), "stone");
+    }
+
+    #[test]
+    fn derive_with_new_parts() {
+        let mut id = Identifier::<()>::parse("stone").unwrap();
+        assert_eq!(id.with_value("dirt").unwrap(), "unspecified:dirt");
+        assert_eq!(id.with_namespace("").unwrap(), id);
+        assert!(matches!(
+            id.with_namespace("G"),
+            Err(ParseError::IllegalCharsInNamespace(..))
+        ));
+
+        id.set_value("item/sword").unwrap();
+        assert_eq!(id.value(), "item/sword");
+        assert!(matches!(id.set_value(""), Err(ParseError::EmptyValue)));
+        assert!(id.set_value("b@d").is_err());
+        assert_eq!(id, "unspecified:item/sword");
+    }
+
+    #[test]
+    fn prefix_and_suffix() {
+        let id = Identifier::<()>::parse("game:lamp").unwrap();
+        assert_eq!(id.with_value_prefix("").unwrap(), id);
+        assert!(matches!(
+            id.with_value_suffix(" on"),
+            Err(ParseError::IllegalCharsInValue(value, bad))
+                if value == "lamp on" && bad == [(4, ' ')]
+        ));
+    }
+
+    #[test]
+    fn display_flags() {
+        let id = Identifier::<()>::parse("game:stone").unwrap();
+        assert_eq!(format!("{id}"), "game:stone");
+        assert_eq!(format!("{id:>12}|"), "  game:stone|");
+        assert_eq!(format!("{id:-<12}|"), "game:stone--|");
+        assert_eq!(format!("{id:^14.4}|"), "     game     |");
+        assert_eq!(format!("{id:#}"), "game:stone");
+
+        let id = Identifier::<()>::parse("stone").unwrap();
+        assert_eq!(format!("{id}"), format!("{DEFAULT_NAMESPACE}:stone"));
+        assert_eq!(format!("{id:>#7}"), "  stone");
+    }
+
+    #[test]
+    fn debug() {
+        struct Block;
+
+        let id = Identifier::<Block>::parse("game:stone").unwrap();
+        assert_eq!(format!("{id:?}"), r#"Identifier<Block>("game:stone")"#);
+        let id = Identifier::<Vec<Block>>::parse("game:stone").unwrap();
+        assert_eq!(
+            format!("{id:?}"),
+            r#"Identifier<Vec<Block>>("game:stone")"#
+        );
+        let id = Identifier::<()>::parse("game:stone").unwrap();
+        assert_eq!(format!("{id:?}"), r#"Identifier<()>("game:stone")"#);
+    }
+
+    #[test]
+    fn marker_does_not_affect_auto_traits() {
+        fn assert_auto_traits<T: Send + Sync + Unpin>() {}
+        assert_auto_traits::<Identifier<std::rc::Rc<()>>>();
+        assert_auto_traits::<Identifier<std::marker::PhantomPinned>>();
+    }
+
+    #[test]
+    fn is_one_pointer() {
+        use std::mem::size_of;
+
+        assert_eq!(size_of::<Identifier<()>>(), size_of::<usize>());
+        assert_eq!(size_of::<Option<Identifier<()>>>(), size_of::<usize>());
+    }
+
+    #[test]
+    fn hash_follows_set_value() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let state = RandomState::new();
+        let mut id = Identifier::<()>::parse("game:a").unwrap();
+        id.set_value("b").unwrap();
+        let expected = Identifier::<()>::parse("game:b").unwrap();
+        assert_eq!(state.hash_one(&id), state.hash_one(&expected));
+        assert_eq!(id.stable_hash64(), expected.stable_hash64());
+    }
+
+    #[test]
+    fn set_value_does_not_affect_clones() {
+        let mut id = Identifier::<()>::parse("game:a").unwrap();
+        let copy = id.clone();
+        id.set_value("b").unwrap();
+        assert_eq!((id.as_str(), copy.as_str()), ("game:b", "game:a"));
+        assert_eq!(copy.into_value(), "a");
     }
 
     #[test]
@@ -313,6 +1640,254 @@ mod tests {
         }
     }
 
+    #[test]
+    fn underline_points_at_bad_chars() {
+        let input = "b@d/ns:stone";
+        let err = Identifier::<()>::from_str(input).unwrap_err();
+        assert_eq!(err.underline(input), "b@d/ns:stone\n ^ ^");
+
+        let input = "ns:";
+        let err = Identifier::<()>::from_str(input).unwrap_err();
+        assert_eq!(err.underline(input), "ns:\n   ^");
+    }
+
+    #[test]
+    fn parse_bytes_matches_parse() {
+        for input in
+            ["game:stone", "stone", ":stone", "a:b:c", "game:", "B@d:x"]
+        {
+            let expected = Identifier::<()>::parse(input);
+            let actual = Identifier::<()>::parse_bytes(input.as_bytes());
+            assert_eq!(
+                actual.map_err(|e| e.to_string()),
+                expected.map_err(|e| e.to_string()),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_bytes_invalid_utf8() {
+        let err = Identifier::<()>::parse_bytes(b"game:ok\xc3(").unwrap_err();
+        match err {
+            ParseError::InvalidUtf8(err) => assert_eq!(err.valid_up_to(), 7),
+            other => panic!("expected InvalidUtf8, got {other:?}"),
+        }
+        // Valid non-ASCII UTF-8 is reported like in `parse`.
+        assert!(matches!(
+            Identifier::<()>::parse_bytes("game:größe".as_bytes()),
+            Err(ParseError::NonAscii { index: 7 })
+        ));
+    }
+
+    #[test]
+    fn non_ascii() {
+        let input = "gäme:größe";
+        let err = Identifier::<()>::parse(input).unwrap_err();
+        assert!(matches!(err, ParseError::NonAscii { index: 1 }));
+        assert_eq!(err.to_string(), "non-ASCII byte at index 1");
+        assert_eq!(err.underline(input), "gäme:größe\n ^");
+
+        let err = Identifier::<()>::parse("größe").unwrap_err();
+        assert!(matches!(err, ParseError::NonAscii { index: 2 }));
+        // `new` takes the parts as they are and reports them in full.
+        assert!(matches!(
+            Identifier::<()>::new("game", "größe"),
+            Err(ParseError::IllegalCharsInValue(..))
+        ));
+    }
+
+    #[test]
+    fn parse_folded() {
+        let (id, folded) =
+            Identifier::<()>::parse_folded("Game:Item/IronSword").unwrap();
+        assert_eq!(id.to_string(), "game:item/ironsword");
+        assert!(folded);
+
+        let (_, folded) =
+            Identifier::<()>::parse_folded("game:iron_sword").unwrap();
+        assert!(!folded);
+
+        // Only ASCII is folded; everything else is still validated.
+        assert!(matches!(
+            Identifier::<()>::parse_folded("game:Ünïcode"),
+            Err(ParseError::NonAscii { index: 5 })
+        ));
+    }
+
+    #[test]
+    fn parse_with_default_namespace() {
+        for input in ["stone", ":stone"] {
+            let id =
+                Identifier::<()>::parse_with_default(input, "game").unwrap();
+            assert_eq!(id.to_string(), "game:stone");
+        }
+        assert!(matches!(
+            Identifier::<()>::parse_with_default("stone", "Game"),
+            Err(ParseError::IllegalCharsInNamespace(..))
+        ));
+    }
+
+    #[test]
+    fn parse_strict_requires_namespace() {
+        let id = Identifier::<()>::parse_strict("game:stone").unwrap();
+        assert_eq!(id.namespace(), "game");
+        for input in ["stone", ":stone", ""] {
+            let err = Identifier::<()>::parse_strict(input).unwrap_err();
+            assert!(matches!(err, ParseError::MissingNamespace), "{input}");
+        }
+        assert!(matches!(
+            Identifier::<()>::parse_strict("game:"),
+            Err(ParseError::EmptyValue)
+        ));
+        let err = Identifier::<()>::parse_strict(":stone").unwrap_err();
+        assert_eq!(err.underline(":stone"), ":stone\n^");
+    }
+
+    #[test]
+    fn validate_reports_every_problem() {
+        assert!(Identifier::<()>::validate("game:item/sword").is_ok());
+
+        let report = Identifier::<()>::validate("B@d:Ba d").unwrap_err();
+        match report.problems() {
+            [
+                ParseError::IllegalCharsInNamespace(ns, bad_ns),
+                ParseError::IllegalCharsInValue(val, bad_val),
+            ] => {
+                assert_eq!(
+                    (ns.as_str(), bad_ns.as_slice()),
+                    ("B@d", &[(0, 'B'), (1, '@')][..])
+                );
+                assert_eq!(
+                    (val.as_str(), bad_val.as_slice()),
+                    ("Ba d", &[(0, 'B'), (2, ' ')][..])
+                );
+            }
+            other => panic!("unexpected problems: {other:?}"),
+        }
+
+        let report = Identifier::<()>::validate("b@d:").unwrap_err();
+        assert_eq!(
+            report.to_string(),
+            "illegal character(s) in namespace \"b@d\": `@`@1; empty value"
+        );
+    }
+
+    #[test]
+    fn parse_lossy() {
+        let (id, changed) =
+            Identifier::<()>::parse_lossy("My Mod:Item/Ünïcode").unwrap();
+        assert_eq!(id.to_string(), "my_mod:item/_n_code");
+        assert!(changed);
+
+        let (id, changed) = Identifier::<()>::parse_lossy("stone").unwrap();
+        assert_eq!(id.namespace(), DEFAULT_NAMESPACE);
+        assert!(!changed);
+
+        // Only the first separator splits; later ones are repaired.
+        let (id, _) = Identifier::<()>::parse_lossy("a:b:c").unwrap();
+        assert_eq!(id.value(), "b_c");
+
+        assert!(matches!(
+            Identifier::<()>::parse_lossy("game:"),
+            Err(ParseError::EmptyValue)
+        ));
+    }
+
+    #[test]
+    fn slugify() {
+        let slug = |text| {
+            Identifier::<()>::slugify("game", text)
+                .unwrap()
+                .value()
+                .to_owned()
+        };
+        assert_eq!(slug("My Cool Item!"), "my_cool_item");
+        assert_eq!(slug("  --Déjà  vu's #2--"), "dj_vu_s_2");
+        assert_eq!(slug("a/b.c"), "a_b_c");
+        assert!(matches!(
+            Identifier::<()>::slugify("game", "!!! ¿¡"),
+            Err(ParseError::EmptyValue)
+        ));
+        assert!(matches!(
+            Identifier::<()>::slugify("Game", "x"),
+            Err(ParseError::IllegalCharsInNamespace(..))
+        ));
+    }
+
+    #[test]
+    fn stable_hash64_is_fnv1a_of_canonical_form() {
+        let id = Identifier::<()>::from_str("game:stone").unwrap();
+        assert_eq!(id.stable_hash64(), 0x4d30_a910_1063_2a6c);
+
+        let other = Identifier::<()>::from_str("gam:estone").unwrap();
+        assert_ne!(id.stable_hash64(), other.stable_hash64());
+        let typed = Identifier::<u8>::from_str("game:stone").unwrap();
+        assert_eq!(id.stable_hash64(), typed.stable_hash64());
+    }
+
+    #[test]
+    fn underline_too_long() {
+        let limits = LengthLimits {
+            value: Some(3),
+            ..LengthLimits::UNLIMITED
+        };
+        let err = limits.check("game", "stone").unwrap_err();
+        assert_eq!(err.underline("game:stone"), "game:stone\n        ^");
+    }
+
+    #[test]
+    fn underline_structural() {
+        for (input, marks) in [
+            ("game:/a", "     ^"),
+            ("game:a/", "      ^"),
+            ("game:a//b", "       ^"),
+        ] {
+            let err = ParseOptions::new()
+                .structural(true)
+                .parse::<()>(input)
+                .unwrap_err();
+            assert_eq!(err.underline(input), format!("{input}\n{marks}"));
+        }
+    }
+
+    #[test]
+    fn char_table_matches_set() {
+        for ch in (0..=0x2ffu32).filter_map(char::from_u32) {
+            assert_eq!(
+                super::value_table().contains(ch),
+                super::legal_value_chars().contains(&ch),
+                "{ch:?}"
+            );
+            assert_eq!(
+                super::namespace_table().contains(ch),
+                super::legal_namespace_chars().contains(&ch),
+                "{ch:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn fast_path_agrees_with_scalar_path() {
+        let table = super::value_table();
+        for input in [
+            "",
+            "stone",
+            "item/iron_sword.v2-final",
+            "item/iron_sword.v2-fin@l",
+            "abcdefgh",
+            "abcdefgH",
+            "abcdefghi!",
+            "größe",
+        ] {
+            assert_eq!(
+                table.contains_all_ascii(input.as_bytes()),
+                input.chars().all(|ch| table.contains(ch)),
+                "{input}"
+            );
+        }
+    }
+
     #[test]
     fn parse_empty_value() {
         let input = "namespace:";
@@ -0,0 +1,200 @@
+//! Optional maximum lengths, so untrusted input cannot produce huge
+//! identifiers.
+//!
+//! No limits apply unless the application installs some with
+//! [`set_length_limits`], typically once at startup. Once installed, they
+//! are enforced by [`Identifier::new`], [`Identifier::parse`] and every
+//! other constructor that validates its input.
+//!
+//! [`Identifier::new`]: crate::Identifier::new
+//! [`Identifier::parse`]: crate::Identifier::parse
+
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    sync::OnceLock,
+};
+
+use crate::ParseError;
+
+static LIMITS: OnceLock<LengthLimits> = OnceLock::new();
+
+/// Maximum lengths, in bytes, for the parts of an identifier. `None` means
+/// unlimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LengthLimits {
+    /// The maximum length of the namespace.
+    pub namespace: Option<usize>,
+    /// The maximum length of the value.
+    pub value: Option<usize>,
+    /// The maximum length of the whole `namespace:value` form.
+    pub identifier: Option<usize>,
+}
+
+impl LengthLimits {
+    /// No limits at all; the default.
+    pub const UNLIMITED: Self = Self {
+        namespace: None,
+        value: None,
+        identifier: None,
+    };
+
+    /// The limit vanilla Minecraft enforces: the whole identifier must fit
+    /// in a 32767 character protocol string.
+    pub const MINECRAFT: Self = Self {
+        namespace: None,
+        value: None,
+        identifier: Some(32767),
+    };
+
+    /// Checks `namespace` and `value` against these limits.
+    ///
+    /// An empty `namespace` is measured as
+    /// [`DEFAULT_NAMESPACE`](crate::DEFAULT_NAMESPACE), which is what it
+    /// becomes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::{
+    ///     ParseError,
+    ///     limits::{LengthLimits, LengthOf},
+    /// };
+    ///
+    /// let limits = LengthLimits {
+    ///     value: Some(8),
+    ///     ..LengthLimits::UNLIMITED
+    /// };
+    /// assert!(limits.check("game", "stone").is_ok());
+    /// assert!(matches!(
+    ///     limits.check("game", "cobblestone"),
+    ///     Err(ParseError::TooLong { of: LengthOf::Value, limit: 8, len: 11 })
+    /// ));
+    /// ```
+    pub fn check(
+        &self,
+        namespace: &str,
+        value: &str,
+    ) -> Result<(), ParseError> {
+        let namespace_len = if namespace.is_empty() {
+            crate::DEFAULT_NAMESPACE.len()
+        } else {
+            namespace.len()
+        };
+        let checks = [
+            (LengthOf::Namespace, self.namespace, namespace_len),
+            (LengthOf::Value, self.value, value.len()),
+            (
+                LengthOf::Identifier,
+                self.identifier,
+                namespace_len + 1 + value.len(),
+            ),
+        ];
+        for (of, limit, len) in checks {
+            if let Some(limit) = limit
+                && len > limit
+            {
+                return Err(ParseError::TooLong { of, limit, len });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for LengthLimits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+/// Which length a [`ParseError::TooLong`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LengthOf {
+    /// The namespace.
+    Namespace,
+    /// The value.
+    Value,
+    /// The whole `namespace:value` form.
+    Identifier,
+}
+
+impl Display for LengthOf {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(match self {
+            LengthOf::Namespace => "namespace",
+            LengthOf::Value => "value",
+            LengthOf::Identifier => "identifier",
+        })
+    }
+}
+
+/// Installs the process-wide length limits.
+///
+/// Limits can only be installed once, and only before the first identifier
+/// is validated; otherwise `limits` is handed back unchanged.
+pub fn set_length_limits(limits: LengthLimits) -> Result<(), LengthLimits> {
+    LIMITS.set(limits)
+}
+
+/// Returns the process-wide length limits.
+pub fn length_limits() -> &'static LengthLimits {
+    LIMITS.get_or_init(LengthLimits::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LengthLimits, LengthOf};
+    use crate::ParseError;
+
+    #[test]
+    fn unlimited_accepts_anything() {
+        let value = "a".repeat(1 << 20);
+        assert!(LengthLimits::UNLIMITED.check("game", &value).is_ok());
+    }
+
+    #[test]
+    fn reports_first_exceeded_limit() {
+        let limits = LengthLimits {
+            namespace: Some(4),
+            value: Some(8),
+            identifier: Some(12),
+        };
+        assert!(limits.check("game", "stone").is_ok());
+        assert!(matches!(
+            limits.check("gamez", "stone"),
+            Err(ParseError::TooLong {
+                of: LengthOf::Namespace,
+                limit: 4,
+                len: 5
+            })
+        ));
+        assert!(matches!(
+            limits.check("game", "stone123"),
+            Err(ParseError::TooLong {
+                of: LengthOf::Identifier,
+                limit: 12,
+                len: 13
+            })
+        ));
+    }
+
+    #[test]
+    fn empty_namespace_counts_as_default() {
+        let limits = LengthLimits {
+            namespace: Some(4),
+            ..LengthLimits::UNLIMITED
+        };
+        assert!(limits.check("", "stone").is_err());
+    }
+
+    #[test]
+    fn minecraft_limit() {
+        let value = "a".repeat(32767 - "game:".len());
+        assert!(LengthLimits::MINECRAFT.check("game", &value).is_ok());
+        let value = value + "a";
+        let err = LengthLimits::MINECRAFT.check("game", &value).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "identifier is 32768 bytes long, the maximum is 32767"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Things that belong to a namespace, and minting identifiers in one.
+
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    marker::PhantomData,
+};
+
+use crate::{
+    Charset, DEFAULT_NAMESPACE, Identifier, ParseError, Rules,
+    intern::Namespace,
+};
+
+/// Something that owns a namespace, like a plugin or a mod.
+pub trait Namespaced {
+    /// Returns the namespace.
+    fn namespace(&self) -> &str;
+}
+
+impl Namespaced for str {
+    fn namespace(&self) -> &str {
+        self
+    }
+}
+
+impl Namespaced for String {
+    fn namespace(&self) -> &str {
+        self
+    }
+}
+
+impl<T> Namespaced for Identifier<T> {
+    fn namespace(&self) -> &str {
+        Identifier::namespace(self)
+    }
+}
+
+impl<T> Identifier<T> {
+    /// Builds an [`Identifier`] from a value in the namespace of `owner`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::{IdentifierUntyped, namespaced::Namespaced};
+    ///
+    /// struct Plugin;
+    ///
+    /// impl Namespaced for Plugin {
+    ///     fn namespace(&self) -> &str {
+    ///         "teleport"
+    ///     }
+    /// }
+    ///
+    /// let id = IdentifierUntyped::in_namespace_of(&Plugin, "home").unwrap();
+    /// assert_eq!(id, "teleport:home");
+    /// ```
+    pub fn in_namespace_of<N: Namespaced + ?Sized, S: Into<String>>(
+        owner: &N,
+        value: S,
+    ) -> Result<Self, ParseError> {
+        Self::new_in(
+            owner.namespace().to_owned(),
+            value.into(),
+            Charset::STRICT,
+        )
+    }
+}
+
+/// Mints identifiers in one namespace, validated once up front.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{ParseError, namespaced::KeyFactory};
+///
+/// let keys = KeyFactory::<()>::new("teleport").unwrap();
+/// assert_eq!(keys.key("home").unwrap(), "teleport:home");
+/// assert_eq!(keys.key("warps/spawn").unwrap(), "teleport:warps/spawn");
+/// assert!(matches!(keys.key(""), Err(ParseError::EmptyValue)));
+/// assert!(KeyFactory::<()>::new("Teleport").is_err());
+/// ```
+pub struct KeyFactory<T = ()> {
+    namespace: Namespace,
+    type_marker: PhantomData<fn() -> T>,
+}
+
+impl<T> KeyFactory<T> {
+    /// Creates a factory for `namespace`, which defaults to
+    /// [`DEFAULT_NAMESPACE`] if empty.
+    pub fn new(namespace: &str) -> Result<Self, ParseError> {
+        let namespace = if namespace.is_empty() {
+            DEFAULT_NAMESPACE
+        } else {
+            namespace
+        };
+        let rules = Rules::global(Charset::STRICT);
+        rules.limits.check(namespace, "")?;
+        rules.check_namespace(namespace)?;
+        Ok(Self {
+            namespace: Namespace::new(namespace),
+            type_marker: PhantomData,
+        })
+    }
+
+    /// Builds an [`Identifier`] from `value` in this factory's namespace.
+    pub fn key(&self, value: &str) -> Result<Identifier<T>, ParseError> {
+        if value.is_empty() {
+            return Err(ParseError::EmptyValue);
+        }
+        let rules = Rules::global(Charset::STRICT);
+        rules.limits.check(&self.namespace, value)?;
+        rules.check_value(value)?;
+        Ok(Identifier::from_parts(self.namespace.clone(), value))
+    }
+}
+
+impl<T> Namespaced for KeyFactory<T> {
+    fn namespace(&self) -> &str {
+        &self.namespace
+    }
+}
+
+impl<T> Clone for KeyFactory<T> {
+    fn clone(&self) -> Self {
+        Self {
+            namespace: self.namespace.clone(),
+            type_marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for KeyFactory<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("KeyFactory")
+            .field(&&*self.namespace)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyFactory, Namespaced};
+    use crate::{Identifier, IdentifierUntyped, ParseError};
+
+    struct Block;
+
+    #[test]
+    fn factory() {
+        let blocks = KeyFactory::<Block>::new("game").unwrap();
+        let stone: Identifier<Block> = blocks.key("stone").unwrap();
+        assert_eq!(stone, Identifier::parse("game:stone").unwrap());
+        assert!(matches!(
+            blocks.key("Stone"),
+            Err(ParseError::IllegalCharsInValue(..))
+        ));
+        let copy = blocks.clone();
+        assert_eq!(copy.namespace(), "game");
+        assert_eq!(copy.key("dirt").unwrap(), "game:dirt");
+        assert_eq!(format!("{blocks:?}"), r#"KeyFactory("game")"#);
+
+        let default = KeyFactory::<()>::new("").unwrap();
+        assert_eq!(default.namespace(), crate::DEFAULT_NAMESPACE);
+        assert!(matches!(
+            KeyFactory::<()>::new("a:b"),
+            Err(ParseError::IllegalCharsInNamespace(..))
+        ));
+    }
+
+    #[test]
+    fn in_namespace_of() {
+        let stone = IdentifierUntyped::parse("game:stone").unwrap();
+        let dirt = IdentifierUntyped::in_namespace_of(&stone, "dirt").unwrap();
+        assert_eq!(dirt, "game:dirt");
+        let id = IdentifierUntyped::in_namespace_of("mod", "ruby").unwrap();
+        assert_eq!(id, "mod:ruby");
+        assert!(IdentifierUntyped::in_namespace_of("mod", "a:b").is_err());
+    }
+}
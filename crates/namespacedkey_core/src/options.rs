@@ -0,0 +1,403 @@
+//! Configurable parsing, for callers that need something other than the
+//! defaults of [`Identifier::parse`].
+//!
+//! [`Identifier::parse`]: crate::Identifier::parse
+
+use crate::{
+    Charset, DEFAULT_NAMESPACE, DEFAULT_SEPARATOR, Identifier, ParseError,
+    Rules, intern::Namespace, limits::LengthLimits,
+};
+
+/// Options for parsing an [`Identifier`].
+///
+/// The defaults match [`Identifier::parse`].
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, options::ParseOptions};
+///
+/// let options = ParseOptions::new()
+///     .default_namespace("game")
+///     .structural(true);
+/// let id: IdentifierUntyped = options.parse("item/sword").unwrap();
+/// assert_eq!(id.to_string(), "game:item/sword");
+/// assert!(options.parse::<()>("item//sword").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions<'a> {
+    default_namespace: Option<&'a str>,
+    require_namespace: bool,
+    structural: bool,
+    charset: Charset,
+}
+
+impl<'a> ParseOptions<'a> {
+    /// Returns the default options.
+    pub const fn new() -> Self {
+        Self {
+            default_namespace: None,
+            require_namespace: false,
+            structural: false,
+            charset: Charset::STRICT,
+        }
+    }
+
+    /// Uses `namespace` instead of [`DEFAULT_NAMESPACE`] when the input has
+    /// no namespace.
+    ///
+    /// [`DEFAULT_NAMESPACE`]: crate::DEFAULT_NAMESPACE
+    pub const fn default_namespace(mut self, namespace: &'a str) -> Self {
+        self.default_namespace = Some(namespace);
+        self
+    }
+
+    /// Fails with [`ParseError::MissingNamespace`] when the input has no
+    /// namespace, instead of defaulting it.
+    pub const fn require_namespace(mut self, require: bool) -> Self {
+        self.require_namespace = require;
+        self
+    }
+
+    /// Treats the value as a `/`-separated path and rejects leading and
+    /// trailing slashes and empty segments, with
+    /// [`ParseError::LeadingSlash`], [`ParseError::TrailingSlash`] and
+    /// [`ParseError::EmptySegment`].
+    pub const fn structural(mut self, structural: bool) -> Self {
+        self.structural = structural;
+        self
+    }
+
+    /// Also accepts ASCII uppercase letters (`A-Z`) in the namespace and
+    /// value. They are kept as-is, not lowercased.
+    pub const fn allow_uppercase(mut self, allow: bool) -> Self {
+        self.charset.uppercase = allow;
+        self
+    }
+
+    /// Also accepts non-ASCII alphanumeric characters in the namespace and
+    /// value. The input is first normalized to NFC, so errors refer to the
+    /// normalized input; ASCII characters still follow the usual rules.
+    #[cfg(feature = "unicode")]
+    pub const fn allow_unicode(mut self, allow: bool) -> Self {
+        self.charset.unicode = allow;
+        self
+    }
+
+    /// Parses `s` with these options.
+    pub fn parse<T>(&self, s: &str) -> Result<Identifier<T>, ParseError> {
+        #[cfg(feature = "unicode")]
+        if self.charset.unicode {
+            use unicode_normalization::UnicodeNormalization;
+
+            let s: String = s.nfc().collect();
+            return self.parse_normalized(&s);
+        }
+        self.parse_normalized(s)
+    }
+
+    fn parse_normalized<T>(
+        &self,
+        s: &str,
+    ) -> Result<Identifier<T>, ParseError> {
+        Rules::global(self.charset).check_ascii(s)?;
+        let (namespace, value) =
+            s.split_once(DEFAULT_SEPARATOR).unwrap_or(("", s));
+        let namespace = match namespace {
+            "" if self.require_namespace => {
+                return Err(ParseError::MissingNamespace);
+            }
+            "" => self.default_namespace.unwrap_or(""),
+            namespace => namespace,
+        };
+
+        let id = Identifier::new_in(
+            namespace.to_owned(),
+            value.to_owned(),
+            self.charset,
+        )?;
+        if self.structural {
+            check_structure(id.value())?;
+        }
+        Ok(id)
+    }
+}
+
+/// [`ParseOptions`] compiled for parsing many identifiers in a row.
+///
+/// Building a `Validator` looks up the charsets and length limits, and
+/// validates and interns the default namespace, once instead of on every
+/// call.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{
+///     IdentifierUntyped,
+///     options::{ParseOptions, Validator},
+/// };
+///
+/// let validator =
+///     Validator::new(ParseOptions::new().default_namespace("game")).unwrap();
+/// for line in ["stone", "dirt", "other:sand"] {
+///     let id: IdentifierUntyped = validator.parse(line).unwrap();
+///     assert!(id.to_string().ends_with(line));
+/// }
+/// assert!(validator.validate("Stone").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Validator {
+    rules: Rules,
+    default_namespace: Namespace,
+    custom_default: bool,
+    require_namespace: bool,
+    structural: bool,
+}
+
+impl Validator {
+    /// Compiles `options`, failing if its default namespace is invalid.
+    pub fn new(options: ParseOptions<'_>) -> Result<Self, ParseError> {
+        let rules = Rules::global(options.charset);
+        let (default_namespace, custom_default) =
+            match options.default_namespace {
+                Some(namespace) if !namespace.is_empty() => {
+                    rules.check_namespace(namespace)?;
+                    (namespace, true)
+                }
+                _ => (DEFAULT_NAMESPACE, false),
+            };
+        Ok(Self {
+            rules,
+            default_namespace: Namespace::new(default_namespace),
+            custom_default,
+            require_namespace: options.require_namespace,
+            structural: options.structural,
+        })
+    }
+
+    /// Uses `limits` instead of the process-wide
+    /// [length limits](crate::limits::length_limits).
+    pub fn with_length_limits(mut self, limits: LengthLimits) -> Self {
+        self.rules.limits = limits;
+        self
+    }
+
+    /// Checks whether `s` would parse, without building an [`Identifier`].
+    pub fn validate(&self, s: &str) -> Result<(), ParseError> {
+        self.normalized(s, |s| self.check(s).map(|_| ()))
+    }
+
+    /// Parses `s`.
+    pub fn parse<T>(&self, s: &str) -> Result<Identifier<T>, ParseError> {
+        self.normalized(s, |s| {
+            let (namespace, value) = self.check(s)?;
+            Ok(Identifier::from_parts(
+                namespace.unwrap_or_else(|| self.default_namespace.clone()),
+                value,
+            ))
+        })
+    }
+
+    /// Runs `f` on `s`, NFC-normalized first if Unicode is allowed.
+    fn normalized<R>(&self, s: &str, f: impl FnOnce(&str) -> R) -> R {
+        #[cfg(feature = "unicode")]
+        if self.rules.charset.unicode {
+            use unicode_normalization::UnicodeNormalization;
+
+            return f(&s.nfc().collect::<String>());
+        }
+        f(s)
+    }
+
+    /// Validates `s`, returning the interned namespace, or `None` for the
+    /// default, and the value.
+    fn check<'s>(
+        &self,
+        s: &'s str,
+    ) -> Result<(Option<Namespace>, &'s str), ParseError> {
+        self.rules.check_ascii(s)?;
+        let (namespace, value) =
+            s.split_once(DEFAULT_SEPARATOR).unwrap_or(("", s));
+        if namespace.is_empty() {
+            if self.require_namespace {
+                return Err(ParseError::MissingNamespace);
+            }
+            // `DEFAULT_NAMESPACE` is exempt from the charset, like in
+            // `Identifier::new`.
+            let namespace = if self.custom_default {
+                self.default_namespace.as_str()
+            } else {
+                ""
+            };
+            self.rules.check(namespace, value)?;
+        } else {
+            self.rules.check(namespace, value)?;
+        }
+        if self.structural {
+            check_structure(value)?;
+        }
+        Ok((
+            (!namespace.is_empty()).then(|| Namespace::new(namespace)),
+            value,
+        ))
+    }
+}
+
+/// Checks that `value` is a path without empty segments.
+pub(crate) fn check_structure(value: &str) -> Result<(), ParseError> {
+    if value.starts_with('/') {
+        return Err(ParseError::LeadingSlash(value.to_owned()));
+    }
+    if let Some(idx) = value.find("//") {
+        return Err(ParseError::EmptySegment(value.to_owned(), idx + 1));
+    }
+    if value.ends_with('/') {
+        return Err(ParseError::TrailingSlash(value.to_owned()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseOptions, Validator};
+    use crate::{
+        DEFAULT_NAMESPACE, IdentifierUntyped, ParseError, limits::LengthLimits,
+    };
+
+    #[test]
+    fn defaults_match_parse() {
+        for input in ["game:stone", "stone", ":stone", "game:/a//b/"] {
+            assert_eq!(
+                ParseOptions::new().parse::<()>(input).unwrap(),
+                IdentifierUntyped::parse(input).unwrap()
+            );
+        }
+        let id: IdentifierUntyped = ParseOptions::new().parse("stone").unwrap();
+        assert_eq!(id.namespace(), DEFAULT_NAMESPACE);
+    }
+
+    #[test]
+    fn structural() {
+        let options = ParseOptions::new().structural(true);
+        assert!(options.parse::<()>("game:item/sword").is_ok());
+        assert!(matches!(
+            options.parse::<()>("game:/a"),
+            Err(ParseError::LeadingSlash(_))
+        ));
+        assert!(matches!(
+            options.parse::<()>("game:a/"),
+            Err(ParseError::TrailingSlash(_))
+        ));
+        assert!(matches!(
+            options.parse::<()>("game:/"),
+            Err(ParseError::LeadingSlash(_))
+        ));
+        match options.parse::<()>("game:a/b//c") {
+            Err(ParseError::EmptySegment(val, idx)) => {
+                assert_eq!((val.as_str(), idx), ("a/b//c", 4));
+            }
+            other => panic!("expected EmptySegment, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn charset_errors_come_first() {
+        let options = ParseOptions::new().structural(true);
+        assert!(matches!(
+            options.parse::<()>("game:/A"),
+            Err(ParseError::IllegalCharsInValue(..))
+        ));
+    }
+
+    #[test]
+    fn allow_uppercase() {
+        let options = ParseOptions::new().allow_uppercase(true);
+        let id: IdentifierUntyped =
+            options.parse("Game:Item/IronSword").unwrap();
+        assert_eq!(id.to_string(), "Game:Item/IronSword");
+        assert!(matches!(
+            options.parse::<()>("game:Ünïcode"),
+            Err(ParseError::NonAscii { index: 5 })
+        ));
+        assert!(matches!(
+            ParseOptions::new().parse::<()>("Game:stone"),
+            Err(ParseError::IllegalCharsInNamespace(..))
+        ));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn allow_unicode() {
+        let options = ParseOptions::new().allow_unicode(true);
+        let id: IdentifierUntyped =
+            options.parse("spiel:schwert/größe").unwrap();
+        assert_eq!(id.value(), "schwert/größe");
+
+        // "é" as `e` + combining acute is normalized to a single char.
+        let id: IdentifierUntyped = options.parse("game:cafe\u{301}").unwrap();
+        assert_eq!(id.value(), "caf\u{e9}");
+
+        let input = "ゲーム:剣 盾";
+        let err = options.parse::<()>(input).unwrap_err();
+        assert!(matches!(err, ParseError::IllegalCharsInValue(..)));
+        assert_eq!(err.underline(input), "ゲーム:剣 盾\n         ^");
+
+        // ASCII still follows the strict rules.
+        assert!(options.parse::<()>("Game:stone").is_err());
+        assert!(ParseOptions::new().parse::<()>("game:größe").is_err());
+    }
+
+    #[test]
+    fn validator_matches_options() {
+        let options = ParseOptions::new()
+            .default_namespace("game")
+            .structural(true)
+            .allow_uppercase(true);
+        let validator = Validator::new(options).unwrap();
+        for input in ["stone", ":Stone", "x:a/b", "x:a//b", "x:", "B@d:a"] {
+            let expected =
+                options.parse::<()>(input).map_err(|e| e.to_string());
+            assert_eq!(validator.validate(input).is_ok(), expected.is_ok());
+            assert_eq!(
+                validator.parse::<()>(input).map_err(|e| e.to_string()),
+                expected,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn validator_rejects_bad_default_namespace() {
+        let options = ParseOptions::new().default_namespace("B@d");
+        assert!(matches!(
+            Validator::new(options),
+            Err(ParseError::IllegalCharsInNamespace(..))
+        ));
+    }
+
+    #[test]
+    fn validator_length_limits() {
+        let validator = Validator::new(ParseOptions::new())
+            .unwrap()
+            .with_length_limits(LengthLimits {
+                value: Some(4),
+                ..LengthLimits::UNLIMITED
+            });
+        assert!(validator.validate("game:dirt").is_ok());
+        assert!(matches!(
+            validator.validate("game:stone"),
+            Err(ParseError::TooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn require_namespace_wins_over_default() {
+        let options = ParseOptions::new()
+            .default_namespace("game")
+            .require_namespace(true);
+        assert!(matches!(
+            options.parse::<()>("stone"),
+            Err(ParseError::MissingNamespace)
+        ));
+    }
+}
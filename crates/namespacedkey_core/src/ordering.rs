@@ -0,0 +1,353 @@
+//! Orderings other than the default namespace-then-value one, for sorted
+//! listings.
+
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::Deref,
+};
+
+use crate::Identifier;
+
+/// Decides how namespaces are ordered relative to each other. Identifiers
+/// in the same namespace are always ordered by value.
+pub trait OrderPolicy {
+    /// Compares two namespaces.
+    fn cmp_namespaces(&self, a: &str, b: &str) -> Ordering;
+}
+
+/// Namespaces in alphabetical order, like [`Identifier`]'s own [`Ord`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Alphabetical;
+
+impl OrderPolicy for Alphabetical {
+    fn cmp_namespaces(&self, a: &str, b: &str) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// The listed namespaces first, in the order given, then every other
+/// namespace alphabetically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Priority<'a>(pub &'a [&'a str]);
+
+impl OrderPolicy for Priority<'_> {
+    fn cmp_namespaces(&self, a: &str, b: &str) -> Ordering {
+        let rank = |namespace: &str| {
+            self.0
+                .iter()
+                .position(|&listed| listed == namespace)
+                .unwrap_or(self.0.len())
+        };
+        rank(a).cmp(&rank(b)).then_with(|| a.cmp(b))
+    }
+}
+
+impl<T> Identifier<T> {
+    /// Compares two identifiers with the namespaces ordered by `policy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::{IdentifierUntyped, ordering::Priority};
+    ///
+    /// let mut ids: Vec<IdentifierUntyped> = ["addon:a", "game:b", "base:c"]
+    ///     .into_iter()
+    ///     .map(|s| IdentifierUntyped::parse(s).unwrap())
+    ///     .collect();
+    /// ids.sort_by(|a, b| a.cmp_with(b, &Priority(&["game"])));
+    /// assert_eq!(ids, ["game:b", "addon:a", "base:c"]);
+    /// ```
+    pub fn cmp_with<P: OrderPolicy + ?Sized>(
+        &self,
+        other: &Self,
+        policy: &P,
+    ) -> Ordering {
+        policy
+            .cmp_namespaces(self.namespace(), other.namespace())
+            .then_with(|| self.value().cmp(other.value()))
+    }
+}
+
+/// An [`Identifier`] whose [`Ord`] follows the policy `P`, for use as a key
+/// in sorted collections.
+///
+/// # Examples
+///
+/// ```
+/// use std::{cmp::Ordering, collections::BTreeSet};
+///
+/// use namespacedkey_core::{
+///     IdentifierUntyped,
+///     ordering::{OrderPolicy, PolicyOrdered, Priority},
+/// };
+///
+/// #[derive(Default)]
+/// struct GameFirst;
+///
+/// impl OrderPolicy for GameFirst {
+///     fn cmp_namespaces(&self, a: &str, b: &str) -> Ordering {
+///         Priority(&["game"]).cmp_namespaces(a, b)
+///     }
+/// }
+///
+/// let set: BTreeSet<PolicyOrdered<GameFirst, ()>> = ["addon:a", "game:b"]
+///     .into_iter()
+///     .map(|s| PolicyOrdered::new(IdentifierUntyped::parse(s).unwrap()))
+///     .collect();
+/// let first = set.first().unwrap();
+/// assert_eq!(first.namespace(), "game");
+/// ```
+pub struct PolicyOrdered<P, T> {
+    id: Identifier<T>,
+    policy: PhantomData<fn() -> P>,
+}
+
+impl<P, T> PolicyOrdered<P, T> {
+    /// Wraps `id`.
+    pub fn new(id: Identifier<T>) -> Self {
+        Self {
+            id,
+            policy: PhantomData,
+        }
+    }
+
+    /// Returns the wrapped identifier.
+    pub fn into_inner(self) -> Identifier<T> {
+        self.id
+    }
+}
+
+impl<P, T> Deref for PolicyOrdered<P, T> {
+    type Target = Identifier<T>;
+
+    fn deref(&self) -> &Identifier<T> {
+        &self.id
+    }
+}
+
+impl<P, T> From<Identifier<T>> for PolicyOrdered<P, T> {
+    fn from(id: Identifier<T>) -> Self {
+        Self::new(id)
+    }
+}
+
+impl<P, T> Clone for PolicyOrdered<P, T> {
+    fn clone(&self) -> Self {
+        Self::new(self.id.clone())
+    }
+}
+
+impl<P, T> Debug for PolicyOrdered<P, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("PolicyOrdered")
+            .field(&self.id.as_str())
+            .finish()
+    }
+}
+
+impl<P, T> PartialEq for PolicyOrdered<P, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<P, T> Eq for PolicyOrdered<P, T> {}
+
+impl<P, T> Hash for PolicyOrdered<P, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<P: OrderPolicy + Default, T> PartialOrd for PolicyOrdered<P, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Namespaces the policy considers equal are ordered alphabetically, so
+/// this stays consistent with [`Eq`].
+impl<P: OrderPolicy + Default, T> Ord for PolicyOrdered<P, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id
+            .cmp_with(&other.id, &P::default())
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+impl<T> Identifier<T> {
+    /// Compares two identifiers like [`Ord`], except that runs of ASCII
+    /// digits compare by their numeric value, so `item2` sorts before
+    /// `item10`.
+    ///
+    /// Identifiers only equal under this comparison, like `v01` and `v1`,
+    /// fall back to [`Ord`], so the result is [`Ordering::Equal`] only for
+    /// equal identifiers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let mut ids: Vec<IdentifierUntyped> = ["game:item10", "game:item2"]
+    ///     .into_iter()
+    ///     .map(|s| IdentifierUntyped::parse(s).unwrap())
+    ///     .collect();
+    /// ids.sort_by(IdentifierUntyped::cmp_natural);
+    /// assert_eq!(ids, ["game:item2", "game:item10"]);
+    /// ```
+    pub fn cmp_natural(&self, other: &Self) -> Ordering {
+        natural_cmp(self.namespace(), other.namespace())
+            .then_with(|| natural_cmp(self.value(), other.value()))
+            .then_with(|| self.cmp(other))
+    }
+}
+
+/// Compares `a` and `b` char by char, but whole digit runs by value.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
+    loop {
+        match (a.first(), b.first()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (x, rest_a) = split_digits(a);
+                let (y, rest_b) = split_digits(b);
+                // Without leading zeros, a longer run is a bigger number.
+                let ordering = x.len().cmp(&y.len()).then_with(|| x.cmp(y));
+                if ordering.is_ne() {
+                    return ordering;
+                }
+                (a, b) = (rest_a, rest_b);
+            }
+            (Some(x), Some(y)) => {
+                if x != y {
+                    return x.cmp(y);
+                }
+                (a, b) = (&a[1..], &b[1..]);
+            }
+        }
+    }
+}
+
+/// Splits the leading digit run off `s`, without its leading zeros.
+fn split_digits(s: &[u8]) -> (&[u8], &[u8]) {
+    let end = s
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(s.len());
+    let (digits, rest) = s.split_at(end);
+    let zeros = digits.iter().take_while(|&&b| b == b'0').count();
+    (&digits[zeros..], rest)
+}
+
+/// Wraps an identifier so that [`Ord`] is
+/// [`cmp_natural`](Identifier::cmp_natural), for use as a key in sorted
+/// collections.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeSet;
+///
+/// use namespacedkey_core::{IdentifierUntyped, ordering::NaturalOrdered};
+///
+/// let set: BTreeSet<_> = ["game:item10", "game:item9"]
+///     .into_iter()
+///     .map(|s| NaturalOrdered(IdentifierUntyped::parse(s).unwrap()))
+///     .collect();
+/// assert_eq!(set.first().unwrap().0, "game:item9");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NaturalOrdered<I>(pub I);
+
+impl<T> PartialOrd for NaturalOrdered<Identifier<T>> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for NaturalOrdered<Identifier<T>> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_natural(&other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cmp::Ordering, collections::BTreeMap};
+
+    use super::{
+        Alphabetical, NaturalOrdered, OrderPolicy, PolicyOrdered, Priority,
+    };
+    use crate::IdentifierUntyped;
+
+    fn ids(inputs: &[&str]) -> Vec<IdentifierUntyped> {
+        inputs
+            .iter()
+            .map(|s| IdentifierUntyped::parse(*s).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn alphabetical_matches_ord() {
+        let mut by_policy = ids(&["b:a", "a:b", "a:a", "c:a"]);
+        let mut by_ord = by_policy.clone();
+        by_policy.sort_by(|a, b| a.cmp_with(b, &Alphabetical));
+        by_ord.sort();
+        assert_eq!(by_policy, by_ord);
+    }
+
+    #[test]
+    fn priority() {
+        let mut list = ids(&["z:a", "b:b", "mod:x", "game:b", "game:a"]);
+        list.sort_by(|a, b| a.cmp_with(b, &Priority(&["game", "mod"])));
+        assert_eq!(list, ["game:a", "game:b", "mod:x", "b:b", "z:a"]);
+    }
+
+    /// Claims every namespace is equal.
+    #[derive(Default)]
+    struct Flat;
+
+    impl OrderPolicy for Flat {
+        fn cmp_namespaces(&self, _: &str, _: &str) -> Ordering {
+            Ordering::Equal
+        }
+    }
+
+    #[test]
+    fn wrapper_stays_consistent_with_eq() {
+        let mut map = BTreeMap::new();
+        for (idx, id) in ids(&["b:a", "a:a", "a:b"]).into_iter().enumerate() {
+            map.insert(PolicyOrdered::<Flat, ()>::new(id), idx);
+        }
+        assert_eq!(map.len(), 3);
+        let keys: Vec<_> = map.keys().map(|id| id.as_str()).collect();
+        assert_eq!(keys, ["a:a", "b:a", "a:b"]);
+    }
+
+    #[test]
+    fn natural() {
+        let mut list =
+            ids(&["a:x10", "a:x2", "a:x02", "a:x", "a:x2b", "b1:a", "b10:a"]);
+        list.sort_by(IdentifierUntyped::cmp_natural);
+        assert_eq!(
+            list,
+            ["a:x", "a:x02", "a:x2", "a:x2b", "a:x10", "b1:a", "b10:a"]
+        );
+    }
+
+    #[test]
+    fn natural_wrapper() {
+        let mut map = BTreeMap::new();
+        for id in ids(&["game:v10", "game:v9", "game:v009"]) {
+            map.insert(NaturalOrdered(id), ());
+        }
+        let keys: Vec<_> = map.keys().map(|id| id.0.as_str()).collect();
+        assert_eq!(keys, ["game:v009", "game:v9", "game:v10"]);
+    }
+}
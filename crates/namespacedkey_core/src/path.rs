@@ -0,0 +1,236 @@
+//! Mapping between identifiers and files in datapack-style directory
+//! layouts, where `game:item/sword` lives at `<root>/game/item/sword.json`.
+
+use std::path::{Component, Path, PathBuf};
+
+use crate::{Identifier, ParseError};
+
+/// Error returned when an [`Identifier`] cannot be mapped to or from a path.
+#[derive(Debug, thiserror::Error)]
+pub enum PathError {
+    /// A namespace or value segment is empty, `.` or `..`, so the path
+    /// would not map back to the same identifier or could escape the root.
+    #[error("{0:?} is not a usable path segment")]
+    UnsafeSegment(String),
+
+    /// The path is not inside the root directory.
+    #[error("path is not under the root directory")]
+    NotUnderRoot,
+
+    /// The path does not have both a namespace and a value component.
+    #[error("path has no namespace or no value")]
+    TooShort,
+
+    /// The file name does not end in the expected extension.
+    #[error("{0:?} does not have the expected extension")]
+    WrongExtension(String),
+
+    /// A path component is not valid UTF-8 or is not a plain name.
+    #[error("path component {0:?} is not a plain UTF-8 name")]
+    BadComponent(PathBuf),
+
+    /// The namespace and value do not form a valid identifier.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+impl<T> Identifier<T> {
+    /// Returns the path of this identifier's file under `root`, as
+    /// `root/<namespace>/<value>.<extension>`. An empty `extension` adds no
+    /// dot.
+    ///
+    /// Each `/` in the value becomes a directory. Fails if the namespace or
+    /// any value segment is empty, `.` or `..`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    /// use std::path::Path;
+    ///
+    /// let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+    /// assert_eq!(
+    ///     id.to_path("data", "json").unwrap(),
+    ///     Path::new("data/game/item/sword.json")
+    /// );
+    /// ```
+    pub fn to_path(
+        &self,
+        root: impl AsRef<Path>,
+        extension: &str,
+    ) -> Result<PathBuf, PathError> {
+        let mut path = root.as_ref().to_path_buf();
+        for segment in
+            std::iter::once(self.namespace()).chain(self.value().split('/'))
+        {
+            if matches!(segment, "" | "." | "..") {
+                return Err(PathError::UnsafeSegment(segment.to_owned()));
+            }
+            path.push(segment);
+        }
+        if !extension.is_empty() {
+            let mut file = path.into_os_string();
+            file.push(".");
+            file.push(extension);
+            path = file.into();
+        }
+        Ok(path)
+    }
+
+    /// Builds an identifier from a file under `root`, the reverse of
+    /// [`to_path`](Self::to_path).
+    ///
+    /// The first component below `root` is the namespace and the rest form
+    /// the value, with `.<extension>` removed from the file name. An empty
+    /// `extension` removes nothing, so values containing `.` survive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id =
+    ///     IdentifierUntyped::from_path("data", "data/game/item/sword.json", "json")
+    ///         .unwrap();
+    /// assert_eq!(id.to_string(), "game:item/sword");
+    /// let id =
+    ///     IdentifierUntyped::from_path("data", "data/game/sword.v2", "").unwrap();
+    /// assert_eq!(id.to_string(), "game:sword.v2");
+    /// ```
+    pub fn from_path(
+        root: impl AsRef<Path>,
+        path: impl AsRef<Path>,
+        extension: &str,
+    ) -> Result<Self, PathError> {
+        let relative = path
+            .as_ref()
+            .strip_prefix(root)
+            .map_err(|_| PathError::NotUnderRoot)?;
+
+        let mut segments = Vec::new();
+        for component in relative.components() {
+            match component {
+                Component::Normal(name) => match name.to_str() {
+                    Some(name) => segments.push(name),
+                    None => {
+                        return Err(PathError::BadComponent(name.into()));
+                    }
+                },
+                other => {
+                    return Err(PathError::BadComponent(
+                        other.as_os_str().into(),
+                    ));
+                }
+            }
+        }
+
+        let [namespace, dirs @ .., file] = segments.as_slice() else {
+            return Err(PathError::TooShort);
+        };
+        let stem = if extension.is_empty() {
+            file
+        } else {
+            file.strip_suffix(extension)
+                .and_then(|stem| stem.strip_suffix('.'))
+                .ok_or_else(|| PathError::WrongExtension((*file).to_owned()))?
+        };
+        let mut value = dirs.join("/");
+        if !value.is_empty() {
+            value.push('/');
+        }
+        value.push_str(stem);
+        Ok(Identifier::new((*namespace).to_owned(), value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::PathError;
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[test]
+    fn round_trip() {
+        let root = Path::new("/srv/data");
+        for input in ["game:stone", "game:item/sword", "game:item/sword.v2"] {
+            let id = IdentifierUntyped::parse(input).unwrap();
+            let path = id.to_path(root, "json").unwrap();
+            assert_eq!(
+                IdentifierUntyped::from_path(root, &path, "json").unwrap(),
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn empty_extension() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        assert_eq!(
+            id.to_path("data", "").unwrap(),
+            Path::new("data/game/item/sword")
+        );
+
+        let id = IdentifierUntyped::parse("game:item/sword.v2").unwrap();
+        let path = id.to_path("data", "").unwrap();
+        assert_eq!(path, Path::new("data/game/item/sword.v2"));
+        assert_eq!(
+            IdentifierUntyped::from_path("data", &path, "").unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn to_path_rejects_unsafe_segments() {
+        for input in ["..:stone", "game:../stone", "game:item//sword"] {
+            let id = IdentifierUntyped::parse(input).unwrap();
+            assert!(
+                matches!(
+                    id.to_path("data", "json"),
+                    Err(PathError::UnsafeSegment(_))
+                ),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn from_path_rejects_bad_paths() {
+        assert!(matches!(
+            IdentifierUntyped::from_path(
+                "data",
+                "other/game/stone.json",
+                "json"
+            ),
+            Err(PathError::NotUnderRoot)
+        ));
+        assert!(matches!(
+            IdentifierUntyped::from_path("data", "data/game", "json"),
+            Err(PathError::TooShort)
+        ));
+        assert!(matches!(
+            IdentifierUntyped::from_path(
+                "data",
+                "data/game/../stone.json",
+                "json"
+            ),
+            Err(PathError::BadComponent(_))
+        ));
+        assert!(matches!(
+            IdentifierUntyped::from_path(
+                "data",
+                "data/game/Stone.json",
+                "json"
+            ),
+            Err(PathError::Parse(ParseError::IllegalCharsInValue(..)))
+        ));
+        assert!(matches!(
+            IdentifierUntyped::from_path("data", "data/game/stone.png", "json"),
+            Err(PathError::WrongExtension(_))
+        ));
+        assert!(matches!(
+            IdentifierUntyped::from_path("data", "data/game/stonejson", "json"),
+            Err(PathError::WrongExtension(_))
+        ));
+    }
+}
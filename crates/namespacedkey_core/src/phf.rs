@@ -0,0 +1,290 @@
+//! Perfect-hash sets and maps of identifiers, built at compile time by
+//! `identifier_phf_set!` and `identifier_phf_map!`, for fixed vocabularies
+//! that need O(1) lookups without any runtime construction.
+//!
+//! The tables use the hash-and-displace scheme: every key hashes to a
+//! bucket, and each bucket stores the displacement that sends its keys to
+//! free slots.
+
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+
+use crate::{Identifier, fnv1a};
+
+/// Keys per bucket, trading build time against table size.
+const LAMBDA: usize = 5;
+
+/// How many seeds to try before giving up on a key set.
+const MAX_SEEDS: u64 = 1024;
+
+/// The three hashes of `key` under `seed`: the bucket and two slot hashes.
+fn hashes(key: &str, seed: u64) -> (u32, u32, u32) {
+    let h = mix(fnv1a(key) ^ seed);
+    let h2 = mix(h);
+    ((h >> 32) as u32, h as u32, h2 as u32)
+}
+
+/// The SplitMix64 finalizer, so every output bit depends on every input
+/// bit.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+fn slot(f1: u32, f2: u32, (d1, d2): (u32, u32), len: usize) -> usize {
+    f2.wrapping_add(f1.wrapping_mul(d1)).wrapping_add(d2) as usize % len
+}
+
+/// A perfect-hash table produced by [`build`](__build).
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct __Table {
+    /// The seed the hashes used.
+    pub seed: u64,
+    /// The displacement of each bucket.
+    pub displacements: Vec<(u32, u32)>,
+    /// For every slot, the index of the key placed there.
+    pub order: Vec<usize>,
+}
+
+/// Builds a perfect-hash table for `keys`, which must be distinct. Returns
+/// `None` if no seed worked, which only happens for hash collisions.
+#[doc(hidden)]
+pub fn __build(keys: &[&str]) -> Option<__Table> {
+    let len = keys.len();
+    let buckets_len = len.div_ceil(LAMBDA).max(1);
+    'seeds: for seed in 0..MAX_SEEDS {
+        let hashes: Vec<_> = keys.iter().map(|key| hashes(key, seed)).collect();
+        let mut buckets = vec![Vec::new(); buckets_len];
+        for (idx, &(g, _, _)) in hashes.iter().enumerate() {
+            buckets[g as usize % buckets_len].push(idx);
+        }
+        let mut by_size: Vec<_> = (0..buckets_len).collect();
+        by_size.sort_by_key(|&bucket| std::cmp::Reverse(buckets[bucket].len()));
+
+        let mut order = vec![None; len];
+        let mut displacements = vec![(0, 0); buckets_len];
+        // Slots tried for the current displacement, marked by generation.
+        let mut tried = vec![0_u64; len];
+        let mut generation = 0;
+        for bucket in by_size {
+            let keys = &buckets[bucket];
+            if keys.is_empty() {
+                continue;
+            }
+            let mut placed = false;
+            'displacements: for d1 in 0..len as u32 {
+                for d2 in 0..len as u32 {
+                    generation += 1;
+                    // Every key needs its own free slot.
+                    let free = keys.iter().all(|&key| {
+                        let (_, f1, f2) = hashes[key];
+                        let idx = slot(f1, f2, (d1, d2), len);
+                        let free =
+                            order[idx].is_none() && tried[idx] != generation;
+                        tried[idx] = generation;
+                        free
+                    });
+                    if free {
+                        for &key in keys {
+                            let (_, f1, f2) = hashes[key];
+                            order[slot(f1, f2, (d1, d2), len)] = Some(key);
+                        }
+                        displacements[bucket] = (d1, d2);
+                        placed = true;
+                        break 'displacements;
+                    }
+                }
+            }
+            if !placed {
+                continue 'seeds;
+            }
+        }
+        return Some(__Table {
+            seed,
+            displacements,
+            order: order.into_iter().flatten().collect(),
+        });
+    }
+    None
+}
+
+/// A static map from identifiers to values, from `identifier_phf_map!`.
+///
+/// Keys are stored in their full `namespace:value` form. Lookups hash the
+/// key once and compare it against a single entry.
+pub struct IdentifierMap<V: 'static> {
+    seed: u64,
+    displacements: &'static [(u32, u32)],
+    entries: &'static [(&'static str, V)],
+}
+
+impl<V> IdentifierMap<V> {
+    /// Used by the macros. Not part of the public API.
+    #[doc(hidden)]
+    pub const fn __new(
+        seed: u64,
+        displacements: &'static [(u32, u32)],
+        entries: &'static [(&'static str, V)],
+    ) -> Self {
+        Self {
+            seed,
+            displacements,
+            entries,
+        }
+    }
+
+    fn entry(&self, key: &str) -> Option<&'static (&'static str, V)> {
+        let (g, f1, f2) = hashes(key, self.seed);
+        let buckets = self.displacements.len();
+        let displacement =
+            *self.displacements.get(g as usize % buckets.max(1))?;
+        let idx = slot(f1, f2, displacement, self.entries.len().max(1));
+        self.entries.get(idx).filter(|(stored, _)| *stored == key)
+    }
+
+    /// Returns the value for `id`, if it is a key.
+    pub fn get<T>(&self, id: &Identifier<T>) -> Option<&'static V> {
+        self.get_str(id.as_str())
+    }
+
+    /// Returns the value for the `namespace:value` string `key`, if it is a
+    /// key.
+    pub fn get_str(&self, key: &str) -> Option<&'static V> {
+        self.entry(key).map(|(_, value)| value)
+    }
+
+    /// Returns `true` if `id` is a key.
+    pub fn contains_key<T>(&self, id: &Identifier<T>) -> bool {
+        self.entry(id.as_str()).is_some()
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entries, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &'static V)> {
+        self.entries.iter().map(|(key, value)| (*key, value))
+    }
+
+    /// Returns the keys, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &'static str> {
+        self.entries.iter().map(|(key, _)| *key)
+    }
+}
+
+impl<V> Clone for IdentifierMap<V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for IdentifierMap<V> {}
+
+impl<V: Debug> Debug for IdentifierMap<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// A static set of identifiers, from `identifier_phf_set!`.
+#[derive(Clone, Copy)]
+pub struct IdentifierSet {
+    map: IdentifierMap<()>,
+}
+
+impl IdentifierSet {
+    /// Used by the macros. Not part of the public API.
+    #[doc(hidden)]
+    pub const fn __new(
+        seed: u64,
+        displacements: &'static [(u32, u32)],
+        entries: &'static [(&'static str, ())],
+    ) -> Self {
+        Self {
+            map: IdentifierMap::__new(seed, displacements, entries),
+        }
+    }
+
+    /// Returns `true` if `id` is in the set.
+    pub fn contains<T>(&self, id: &Identifier<T>) -> bool {
+        self.map.contains_key(id)
+    }
+
+    /// Returns `true` if the `namespace:value` string `key` is in the set.
+    pub fn contains_str(&self, key: &str) -> bool {
+        self.map.entry(key).is_some()
+    }
+
+    /// Returns the number of identifiers.
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns the identifiers as strings, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> {
+        self.map.keys()
+    }
+}
+
+impl Debug for IdentifierSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{__build, IdentifierMap};
+    use crate::IdentifierUntyped;
+
+    /// Leaks a map built at runtime, the way the macro lays it out.
+    fn map(keys: &[&'static str]) -> IdentifierMap<usize> {
+        let table = __build(keys).unwrap();
+        let entries: Vec<_> =
+            table.order.iter().map(|&idx| (keys[idx], idx)).collect();
+        IdentifierMap::__new(
+            table.seed,
+            Vec::leak(table.displacements),
+            Vec::leak(entries),
+        )
+    }
+
+    #[test]
+    fn finds_every_key() {
+        let keys: Vec<&'static str> = (0..500)
+            .map(|idx| &*String::leak(format!("game:block_{idx}")))
+            .collect();
+        let map = map(&keys);
+        assert_eq!(map.len(), 500);
+        for (idx, key) in keys.iter().enumerate() {
+            assert_eq!(map.get_str(key), Some(&idx));
+        }
+        assert_eq!(map.get_str("game:block_500"), None);
+        let id = IdentifierUntyped::parse("game:block_7").unwrap();
+        assert_eq!(map.get(&id), Some(&7));
+    }
+
+    #[test]
+    fn small_and_empty() {
+        let one = map(&["game:stone"]);
+        assert_eq!(one.get_str("game:stone"), Some(&0));
+        assert_eq!(one.get_str("game:dirt"), None);
+
+        let empty = map(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.get_str("game:stone"), None);
+    }
+}
@@ -0,0 +1,81 @@
+//! `tokio-postgres` support. Identifiers are stored as their
+//! `namespace:value` string in `TEXT` and `VARCHAR` columns.
+
+use std::error::Error;
+
+use bytes::BytesMut;
+use postgres_types::{FromSql, IsNull, ToSql, Type, accepts, to_sql_checked};
+
+use crate::Identifier;
+
+impl<T> ToSql for Identifier<T> {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        self.as_str().to_sql(ty, out)
+    }
+
+    accepts!(TEXT, VARCHAR);
+
+    to_sql_checked!();
+}
+
+impl<'a, T> FromSql<'a> for Identifier<T> {
+    fn from_sql(
+        ty: &Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let s = <&str as FromSql>::from_sql(ty, raw)?;
+        Ok(Identifier::parse_str(s)?)
+    }
+
+    accepts!(TEXT, VARCHAR);
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, ToSql, Type};
+
+    use crate::{Identifier, IdentifierUntyped};
+
+    #[test]
+    fn round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let mut buf = BytesMut::new();
+        id.to_sql_checked(&Type::TEXT, &mut buf).unwrap();
+        assert_eq!(&buf[..], b"game:item/sword");
+        let decoded = IdentifierUntyped::from_sql(&Type::TEXT, &buf).unwrap();
+        assert_eq!(decoded, id);
+    }
+
+    #[test]
+    fn marker_needs_no_debug() {
+        struct Block;
+
+        let id = Identifier::<Block>::parse("game:stone").unwrap();
+        let mut buf = BytesMut::new();
+        id.to_sql_checked(&Type::VARCHAR, &mut buf).unwrap();
+        let decoded =
+            Identifier::<Block>::from_sql(&Type::VARCHAR, &buf).unwrap();
+        assert_eq!(decoded, id);
+    }
+
+    #[test]
+    fn accepts_text_columns_only() {
+        assert!(<IdentifierUntyped as ToSql>::accepts(&Type::TEXT));
+        assert!(<IdentifierUntyped as ToSql>::accepts(&Type::VARCHAR));
+        assert!(!<IdentifierUntyped as ToSql>::accepts(&Type::INT4));
+        assert!(<IdentifierUntyped as FromSql>::accepts(&Type::VARCHAR));
+        assert!(!<IdentifierUntyped as FromSql>::accepts(&Type::BYTEA));
+    }
+
+    #[test]
+    fn from_sql_rejects_illegal_chars() {
+        let err = IdentifierUntyped::from_sql(&Type::TEXT, b"game:Stone")
+            .unwrap_err();
+        assert!(err.to_string().contains("illegal character(s) in value"));
+    }
+}
@@ -0,0 +1,187 @@
+//! [proptest] strategies for generating identifiers and malformed input.
+//!
+//! # Examples
+//!
+//! ```
+//! use namespacedkey_core::{IdentifierUntyped, proptest::any_identifier};
+//! use proptest::test_runner::TestRunner;
+//!
+//! let mut runner = TestRunner::default();
+//! runner
+//!     .run(&any_identifier::<()>(), |id| {
+//!         let parsed = IdentifierUntyped::parse(id.to_string()).unwrap();
+//!         assert_eq!(parsed, id);
+//!         Ok(())
+//!     })
+//!     .unwrap();
+//! ```
+//!
+//! [proptest]: https://docs.rs/proptest/latest/proptest/
+
+use std::ops::RangeInclusive;
+
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::vec,
+    prelude::*,
+    sample::select,
+    strategy::{BoxedStrategy, Strategy},
+};
+
+use crate::{
+    DEFAULT_SEPARATOR, Identifier,
+    generate::{Alphabet, capped},
+    legal_namespace_chars, legal_value_chars,
+};
+
+/// Generates strings of chars from `chars`, with a length in `len`, cut
+/// short rather than going past `max` bytes.
+fn string_of(
+    chars: Vec<char>,
+    len: RangeInclusive<usize>,
+    max: Option<usize>,
+) -> impl Strategy<Value = String> + use<> {
+    vec(select(chars), len).prop_map(move |chars| capped(chars, max))
+}
+
+/// Generates valid identifiers with arbitrary namespaces and values, drawn
+/// from the configured legal characters. The namespace may be empty, and so
+/// default, where the default namespace fits the
+/// [length limits](crate::limits), and the value is sized to what they
+/// leave after the namespace.
+pub fn any_identifier<T>() -> impl Strategy<Value = Identifier<T>> {
+    let alphabet = Alphabet::configured();
+    let namespace_chars = alphabet.namespace.clone();
+    let len = alphabet.namespace_min()..=16;
+    string_of(namespace_chars, len, alphabet.namespace_max())
+        .prop_flat_map(move |namespace| {
+            let max = alphabet.value_max(&namespace);
+            (
+                Just(namespace),
+                string_of(alphabet.value.clone(), 1..=32, max),
+            )
+        })
+        .prop_filter_map(
+            "generated identifier must be valid",
+            |(namespace, value)| Identifier::new(namespace, value).ok(),
+        )
+}
+
+/// Generates valid identifiers in `namespace`.
+///
+/// `namespace` itself must be legal, otherwise every generated value is
+/// rejected.
+pub fn identifier_in_namespace<T>(
+    namespace: impl Into<String>,
+) -> impl Strategy<Value = Identifier<T>> {
+    let namespace = namespace.into();
+    let alphabet = Alphabet::configured();
+    let max = alphabet.value_max(&namespace);
+    string_of(alphabet.value, 1..=32, max)
+        .prop_filter_map("namespace must be legal", move |value| {
+            Identifier::new(namespace.clone(), value).ok()
+        })
+}
+
+/// Generates strings that [`Identifier::parse`] rejects: empty values and
+/// illegal characters in either the namespace or the value, according to
+/// the configured legal characters.
+pub fn invalid_identifier_string() -> impl Strategy<Value = String> {
+    let Alphabet {
+        namespace, value, ..
+    } = Alphabet::configured();
+    let namespace = move || string_of(namespace.clone(), 0..=8, None);
+    let value = move |len| string_of(value.clone(), len, None);
+    // A separator in the namespace would just end it early.
+    let illegal_in_namespace = illegal_chars(|ch| {
+        ch != DEFAULT_SEPARATOR && !legal_namespace_chars().contains(&ch)
+    });
+    // Includes a second separator, unless that is legal in values.
+    let illegal_in_value =
+        illegal_chars(|ch| !legal_value_chars().contains(&ch));
+    prop_oneof![
+        // Empty value, with or without a namespace.
+        proptest::option::of(namespace()).prop_map(|namespace| namespace
+            .map(|ns| format!("{ns}:"))
+            .unwrap_or_default()),
+        // Illegal characters in the namespace.
+        (
+            namespace(),
+            illegal_in_namespace,
+            namespace(),
+            value(1..=16)
+        )
+            .prop_map(|(before, illegal, after, value)| {
+                format!("{before}{illegal}{after}:{value}")
+            }),
+        // Illegal characters in the value.
+        (namespace(), value(0..=8), illegal_in_value, value(0..=8)).prop_map(
+            |(namespace, before, illegal, after)| {
+                format!("{namespace}:{before}{illegal}{after}")
+            }
+        ),
+    ]
+}
+
+/// Generates one to three chars that `is_illegal` accepts, drawn from
+/// printable ASCII and a few non-ASCII letters.
+fn illegal_chars(
+    is_illegal: fn(char) -> bool,
+) -> impl Strategy<Value = String> {
+    let pool: Vec<char> = (' '..='~')
+        .chain(['é', 'ß', 'Ж', '字'])
+        .filter(|&ch| is_illegal(ch))
+        .collect();
+    string_of(pool, 1..=3, None)
+}
+
+impl<T: 'static> Arbitrary for Identifier<T> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        any_identifier().boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{
+        any_identifier, identifier_in_namespace, invalid_identifier_string,
+    };
+    use crate::IdentifierUntyped;
+
+    struct Block;
+
+    proptest! {
+        #[test]
+        fn any_identifier_round_trips(id in any_identifier::<()>()) {
+            let parsed = IdentifierUntyped::parse(id.to_string()).unwrap();
+            prop_assert_eq!(parsed, id);
+        }
+
+        #[test]
+        fn identifier_in_namespace_keeps_namespace(
+            id in identifier_in_namespace::<()>("game"),
+        ) {
+            prop_assert_eq!(id.namespace(), "game");
+        }
+
+        #[test]
+        fn marker_needs_no_debug(id in any_identifier::<Block>()) {
+            prop_assert!(!id.value().is_empty());
+        }
+
+        #[test]
+        fn invalid_strings_are_rejected(s in invalid_identifier_string()) {
+            prop_assert!(IdentifierUntyped::parse(s).is_err());
+        }
+
+        #[test]
+        fn arbitrary_impl_is_valid(id in any::<IdentifierUntyped>()) {
+            prop_assert!(!id.value().is_empty());
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Helpers for reading and writing identifiers exactly as the vanilla
+//! Minecraft: Java Edition protocol does.
+//!
+//! On the wire an identifier is a protocol `String`: a VarInt byte length
+//! followed by the UTF-8 encoded `namespace:value` form, holding at most
+//! [`MAX_LEN`] UTF-16 code units. Identifiers read from the wire follow the
+//! client's lenient rules (see [`parse`]), so a missing namespace means
+//! [`DEFAULT_NAMESPACE`] rather than [`crate::DEFAULT_NAMESPACE`].
+
+use std::{
+    io::{self, Read, Write},
+    string::FromUtf8Error,
+};
+
+use crate::{
+    Identifier, ParseError,
+    wire::{read_varint, write_varint},
+};
+
+/// The namespace vanilla assumes when none is given.
+pub const DEFAULT_NAMESPACE: &str = "minecraft";
+
+/// The maximum length of a protocol string, in UTF-16 code units.
+pub const MAX_LEN: usize = 32767;
+
+/// The maximum byte length a protocol string's prefix may announce.
+const MAX_BYTES: usize = MAX_LEN * 3;
+
+/// Error returned when an [`Identifier`] cannot be written to or read from
+/// the protocol.
+#[derive(Debug, thiserror::Error)]
+pub enum ProtocolError {
+    /// The underlying reader or writer failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// The string is longer than [`MAX_LEN`] UTF-16 code units, or its
+    /// prefix announces more bytes than such a string could take up.
+    #[error("string length {0} exceeds the protocol maximum of {MAX_LEN}")]
+    TooLong(usize),
+
+    /// The length prefix is not a valid VarInt.
+    #[error("malformed VarInt length prefix")]
+    MalformedVarInt,
+
+    /// The string is not valid UTF-8.
+    #[error(transparent)]
+    InvalidUtf8(#[from] FromUtf8Error),
+
+    /// The string is not a valid identifier.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// Parses `s` the way the vanilla client does.
+///
+/// Unlike [`Identifier::parse`], a missing or empty namespace (`stone` or
+/// `:stone`) becomes [`DEFAULT_NAMESPACE`]. The allowed characters are the
+/// same.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, protocol};
+///
+/// let id: IdentifierUntyped = protocol::parse("stone").unwrap();
+/// assert_eq!(id.to_string(), "minecraft:stone");
+/// let id: IdentifierUntyped = protocol::parse(":stone").unwrap();
+/// assert_eq!(id.to_string(), "minecraft:stone");
+/// ```
+pub fn parse<T>(s: &str) -> Result<Identifier<T>, ParseError> {
+    Identifier::parse_with_default(s, DEFAULT_NAMESPACE)
+}
+
+impl<T> Identifier<T> {
+    /// Writes this identifier as a protocol string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("minecraft:stone").unwrap();
+    /// let mut buf = Vec::new();
+    /// id.write_protocol(&mut buf).unwrap();
+    /// assert_eq!(buf, b"\x0fminecraft:stone");
+    /// assert_eq!(IdentifierUntyped::read_protocol(&mut &buf[..]).unwrap(), id);
+    /// ```
+    pub fn write_protocol<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), ProtocolError> {
+        let s = self.as_str();
+        // Identifiers are ASCII unless `unicode` or a custom charset allows
+        // more, so only count code units when there is something to count.
+        let units = if s.is_ascii() {
+            s.len()
+        } else {
+            s.encode_utf16().count()
+        };
+        if units > MAX_LEN {
+            return Err(ProtocolError::TooLong(units));
+        }
+        // Each code unit takes at most 3 bytes, so the byte length fits in
+        // a u32 after the check above. The prefix counts bytes, not units.
+        write_varint(writer, s.len() as u32)?;
+        writer.write_all(s.as_bytes())?;
+        Ok(())
+    }
+
+    /// Reads a protocol string and parses it with the client's lenient
+    /// rules, see [`parse`].
+    pub fn read_protocol<R: Read + ?Sized>(
+        reader: &mut R,
+    ) -> Result<Self, ProtocolError> {
+        let len = read_varint(reader)?.ok_or(ProtocolError::MalformedVarInt)?
+            as usize;
+        if len > MAX_BYTES {
+            return Err(ProtocolError::TooLong(len));
+        }
+        let mut buf = vec![0; len];
+        reader.read_exact(&mut buf)?;
+        let s = String::from_utf8(buf)?;
+        let units = s.encode_utf16().count();
+        if units > MAX_LEN {
+            return Err(ProtocolError::TooLong(units));
+        }
+        Ok(parse(&s)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_LEN, ProtocolError, parse};
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[test]
+    fn parse_defaults_to_minecraft() {
+        let id: IdentifierUntyped = parse("game:stone").unwrap();
+        assert_eq!(id.namespace(), "game");
+        let id: IdentifierUntyped = parse("stone").unwrap();
+        assert_eq!(id.namespace(), "minecraft");
+        assert!(matches!(
+            parse::<()>("minecraft:"),
+            Err(ParseError::EmptyValue)
+        ));
+    }
+
+    #[test]
+    fn read_is_lenient() {
+        let id =
+            IdentifierUntyped::read_protocol(&mut &b"\x06:stone"[..]).unwrap();
+        assert_eq!(id.to_string(), "minecraft:stone");
+    }
+
+    #[test]
+    fn write_rejects_long_strings() {
+        let value = "a".repeat(MAX_LEN);
+        let id = IdentifierUntyped::new("game", &value).unwrap();
+        let err = id.write_protocol(&mut Vec::new()).unwrap_err();
+        assert!(matches!(err, ProtocolError::TooLong(_)));
+    }
+
+    #[cfg(feature = "unicode")]
+    #[test]
+    fn write_counts_code_units() {
+        use crate::options::ParseOptions;
+
+        let options = ParseOptions::new().allow_unicode(true);
+        let id: IdentifierUntyped = options.parse("spiel:größe").unwrap();
+        let mut buf = Vec::new();
+        id.write_protocol(&mut buf).unwrap();
+        // The prefix is the byte length; "ö" and "ß" take two bytes each.
+        assert_eq!(buf[0], 13);
+        assert_eq!(&buf[1..], "spiel:größe".as_bytes());
+
+        // Under the limit in code units, though over it in bytes.
+        let value = "ß".repeat(MAX_LEN - 2);
+        let id: IdentifierUntyped =
+            options.parse(&format!("a:{value}")).unwrap();
+        assert!(id.write_protocol(&mut Vec::new()).is_ok());
+        let id: IdentifierUntyped =
+            options.parse(&format!("a:{value}ß")).unwrap();
+        let err = id.write_protocol(&mut Vec::new()).unwrap_err();
+        assert!(
+            matches!(err, ProtocolError::TooLong(len) if len == MAX_LEN + 1)
+        );
+    }
+
+    #[test]
+    fn read_rejects_long_strings() {
+        // A prefix of 98302 bytes, more than 32767 code units can take up.
+        let buf = [0xfe, 0xff, 0x05];
+        let err = IdentifierUntyped::read_protocol(&mut &buf[..]).unwrap_err();
+        assert!(matches!(err, ProtocolError::TooLong(98302)));
+
+        let mut buf = vec![0x80, 0x80, 0x02];
+        buf.extend(std::iter::repeat_n(b'a', MAX_LEN + 1));
+        let err = IdentifierUntyped::read_protocol(&mut &buf[..]).unwrap_err();
+        assert!(
+            matches!(err, ProtocolError::TooLong(len) if len == MAX_LEN + 1)
+        );
+    }
+
+    #[test]
+    fn read_validates() {
+        let err = IdentifierUntyped::read_protocol(&mut &b"\x05Stone"[..])
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ProtocolError::Parse(ParseError::IllegalCharsInValue(..))
+        ));
+    }
+}
@@ -0,0 +1,116 @@
+use quickcheck::{Arbitrary, Gen};
+
+use crate::{
+    DEFAULT_NAMESPACE, Identifier,
+    generate::{Alphabet, capped},
+};
+
+/// Picks up to [`Gen::size`] chars from `chars`, at least `min` of them,
+/// stopping early rather than going past `max` bytes.
+fn arbitrary_chars(
+    g: &mut Gen,
+    chars: &[char],
+    min: usize,
+    max: Option<usize>,
+) -> String {
+    let len = (usize::arbitrary(g) % (g.size() + 1)).max(min);
+    capped((0..len).filter_map(|_| g.choose(chars).copied()), max)
+}
+
+/// The shortest identifier the alphabet allows. The chars are sorted, so
+/// the first ones are the shortest in bytes.
+fn minimal<T>(alphabet: &Alphabet) -> Identifier<T> {
+    let namespace: String = if alphabet.namespace_min() == 0 {
+        String::new()
+    } else {
+        alphabet.namespace.iter().take(1).collect()
+    };
+    let value: String = alphabet.value.iter().take(1).collect();
+    Identifier::new_unchecked(namespace, value)
+}
+
+/// Namespaces and values grow with [`Gen::size`], but each is kept short
+/// enough for the installed length limits to accept the pair. Shrinking
+/// moves toward shorter parts, with the default namespace as the smallest
+/// namespace so that it always terminates.
+///
+/// A candidate the limits still reject, which only happens with multi-byte
+/// chars and tight limits, is replaced by the shortest identifier possible.
+/// If the limits leave no room even for that, they are ignored.
+impl<T: 'static> Arbitrary for Identifier<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let alphabet = Alphabet::configured();
+        let namespace = arbitrary_chars(
+            g,
+            &alphabet.namespace,
+            alphabet.namespace_min(),
+            alphabet.namespace_max(),
+        );
+        let value = arbitrary_chars(
+            g,
+            &alphabet.value,
+            1,
+            alphabet.value_max(&namespace),
+        );
+        Identifier::new(namespace, value).unwrap_or_else(|_| minimal(&alphabet))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let namespace = if self.namespace() == DEFAULT_NAMESPACE {
+            String::new()
+        } else {
+            self.namespace_string()
+        };
+        let value = self.value().to_owned();
+
+        let shrunk_namespaces = namespace.shrink().map({
+            let value = value.clone();
+            move |ns| (ns, value.clone())
+        });
+        let shrunk_values = value.shrink().map(move |v| (namespace.clone(), v));
+
+        Box::new(
+            shrunk_namespaces
+                .chain(shrunk_values)
+                .filter_map(|(ns, v)| Identifier::new(ns, v).ok()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::{Arbitrary, Gen, QuickCheck};
+
+    use crate::{DEFAULT_NAMESPACE, IdentifierUntyped};
+
+    #[test]
+    fn arbitrary_identifiers_round_trip() {
+        fn prop(id: IdentifierUntyped) -> bool {
+            IdentifierUntyped::parse(id.to_string()).ok() == Some(id)
+        }
+        QuickCheck::new().quickcheck(prop as fn(IdentifierUntyped) -> bool);
+    }
+
+    #[test]
+    fn shrink_is_shorter_and_valid() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        for shrunk in id.shrink() {
+            assert!(
+                shrunk.namespace() == DEFAULT_NAMESPACE
+                    || shrunk.namespace().len() <= id.namespace().len()
+            );
+            assert!(shrunk.value().len() <= id.value().len());
+            assert!(!shrunk.value().is_empty());
+        }
+    }
+
+    #[test]
+    fn shrink_terminates() {
+        let mut id = IdentifierUntyped::arbitrary(&mut Gen::new(32));
+        while let Some(next) = id.shrink().next() {
+            id = next;
+        }
+        assert_eq!(id.namespace(), DEFAULT_NAMESPACE);
+        assert_eq!(id.value().len(), 1);
+    }
+}
@@ -0,0 +1,211 @@
+//! Streaming identifiers out of newline-separated list files.
+
+use std::{
+    fmt::{Debug, Formatter, Result as FmtResult},
+    io::{self, BufRead},
+    iter::FusedIterator,
+    marker::PhantomData,
+};
+
+use crate::{Identifier, ParseError};
+
+/// The reason a line could not be read.
+#[derive(Debug, thiserror::Error)]
+pub enum ReadError {
+    /// The underlying reader failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// The line is not a valid identifier.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+/// Error returned by [`IdentifierReader`], with the line it happened on.
+#[derive(Debug, thiserror::Error)]
+#[error("line {line}: {error}")]
+pub struct ParseErrorAt {
+    /// The 1-based line number.
+    pub line: usize,
+    /// What went wrong.
+    pub error: ReadError,
+}
+
+/// Reads one identifier per line from a [`BufRead`], without loading the
+/// whole input into memory.
+///
+/// Surrounding whitespace is trimmed, blank lines are skipped, and
+/// everything from a `#` to the end of the line is a comment. An I/O error
+/// is yielded once and ends the iteration.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, reader::IdentifierReader};
+///
+/// let input = "# blocks\ngame:stone\n\n  game:dirt  # soft\ngame:Sand\n";
+/// let mut reader = IdentifierReader::<_, ()>::new(input.as_bytes());
+/// assert_eq!(reader.next().unwrap().unwrap().value(), "stone");
+/// assert_eq!(reader.next().unwrap().unwrap().value(), "dirt");
+/// assert_eq!(reader.next().unwrap().unwrap_err().line, 5);
+/// assert!(reader.next().is_none());
+/// ```
+pub struct IdentifierReader<R, T> {
+    reader: R,
+    line: usize,
+    buf: String,
+    /// Set at the end of the input or after an I/O error.
+    done: bool,
+    type_marker: PhantomData<fn() -> T>,
+}
+
+impl<R: BufRead, T> IdentifierReader<R, T> {
+    /// Creates a reader over `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            buf: String::new(),
+            done: false,
+            type_marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of the last line read.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Consumes this reader, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead, T> Iterator for IdentifierReader<R, T> {
+    type Item = Result<Identifier<T>, ParseErrorAt>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        loop {
+            self.buf.clear();
+            self.line += 1;
+            match self.reader.read_line(&mut self.buf) {
+                Ok(0) => {
+                    self.done = true;
+                    return None;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    // Retrying would most likely fail the same way forever
+                    self.done = true;
+                    return Some(Err(ParseErrorAt {
+                        line: self.line,
+                        error: err.into(),
+                    }));
+                }
+            }
+
+            // `#` is never legal in an identifier, so a comment can start
+            // anywhere on the line.
+            let content = match self.buf.split_once('#') {
+                Some((content, _)) => content.trim(),
+                None => self.buf.trim(),
+            };
+            if content.is_empty() {
+                continue;
+            }
+            return Some(Identifier::parse_str(content).map_err(|err| {
+                ParseErrorAt {
+                    line: self.line,
+                    error: err.into(),
+                }
+            }));
+        }
+    }
+}
+
+impl<R: BufRead, T> FusedIterator for IdentifierReader<R, T> {}
+
+impl<R: Debug, T> Debug for IdentifierReader<R, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("IdentifierReader")
+            .field("reader", &self.reader)
+            .field("line", &self.line)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, BufReader, Read};
+
+    use super::{IdentifierReader, ReadError};
+    use crate::{IdentifierUntyped, ParseError};
+
+    fn read(input: &str) -> Vec<Result<IdentifierUntyped, (usize, String)>> {
+        IdentifierReader::new(input.as_bytes())
+            .map(|r| r.map_err(|err| (err.line, err.error.to_string())))
+            .collect()
+    }
+
+    #[test]
+    fn skips_blanks_and_comments() {
+        let ids = read("\n# header\ngame:a\r\n\t game:b #c\n#\ngame:c");
+        let values: Vec<_> =
+            ids.into_iter().map(|r| r.unwrap().into_value()).collect();
+        assert_eq!(values, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn reports_line_numbers() {
+        let ids = read("game:a\n\ngame:\ngame:b\n");
+        assert!(ids[0].is_ok());
+        assert_eq!(ids[1], Err((3, "empty value".to_owned())));
+        assert!(ids[2].is_ok());
+        assert_eq!(ids.len(), 3);
+    }
+
+    #[test]
+    fn display() {
+        let err = IdentifierReader::<_, ()>::new("x:Y".as_bytes())
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert!(matches!(
+            err.error,
+            ReadError::Parse(ParseError::IllegalCharsInValue(..))
+        ));
+        assert!(err.to_string().starts_with("line 1: illegal character"));
+    }
+
+    struct Broken;
+
+    impl Read for Broken {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("broken"))
+        }
+    }
+
+    #[test]
+    fn io_errors() {
+        let err = IdentifierReader::<_, ()>::new(BufReader::new(Broken))
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(matches!(err.error, ReadError::Io(_)));
+    }
+
+    #[test]
+    fn io_errors_end_iteration() {
+        let mut reader = IdentifierReader::<_, ()>::new(BufReader::new(Broken));
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+        assert!(reader.next().is_none());
+        // Stops rather than looping forever on a persistent error
+        assert_eq!(reader.filter_map(Result::ok).count(), 0);
+    }
+}
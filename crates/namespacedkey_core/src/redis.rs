@@ -0,0 +1,101 @@
+//! Redis support. Identifiers are written as their `namespace:value` string,
+//! so they can be used directly as keys and values.
+//!
+//! Since `:` is also the conventional Redis key separator, an identifier
+//! key naturally nests under its namespace. Use
+//! [`Identifier::as_redis_key_with_prefix`] to scope keys to an application
+//! and [`namespace_pattern`] to `SCAN` all of them in one namespace.
+
+use redis::{FromRedisValue, ParsingError, RedisWrite, ToRedisArgs, Value};
+
+use crate::{DEFAULT_SEPARATOR, Identifier};
+
+impl<T> ToRedisArgs for Identifier<T> {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + RedisWrite,
+    {
+        out.write_arg(self.as_str().as_bytes());
+    }
+}
+
+impl<T> FromRedisValue for Identifier<T> {
+    fn from_redis_value_ref(v: &Value) -> Result<Self, ParsingError> {
+        match v {
+            Value::BulkString(bytes) => parse(std::str::from_utf8(bytes)?),
+            Value::SimpleString(s) => parse(s.as_str()),
+            _ => Self::from_redis_value(v.clone()),
+        }
+    }
+
+    fn from_redis_value(v: Value) -> Result<Self, ParsingError> {
+        parse(String::from_redis_value(v)?)
+    }
+}
+
+fn parse<T>(s: impl Into<String>) -> Result<Identifier<T>, ParsingError> {
+    Identifier::parse(s)
+        .map_err(|err| format!("invalid identifier: {err}").into())
+}
+
+impl<T> Identifier<T> {
+    /// Returns this identifier as a Redis key under `prefix`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:stone").unwrap();
+    /// assert_eq!(id.as_redis_key_with_prefix("cache"), "cache:game:stone");
+    /// ```
+    pub fn as_redis_key_with_prefix(&self, prefix: &str) -> String {
+        format!("{prefix}{DEFAULT_SEPARATOR}{self}")
+    }
+}
+
+/// Returns a `SCAN`/`KEYS` pattern matching every key created by
+/// [`Identifier::as_redis_key_with_prefix`] with `prefix` in `namespace`.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::redis::namespace_pattern;
+///
+/// assert_eq!(namespace_pattern("cache", "game"), "cache:game:*");
+/// ```
+pub fn namespace_pattern(prefix: &str, namespace: &str) -> String {
+    format!("{prefix}{DEFAULT_SEPARATOR}{namespace}{DEFAULT_SEPARATOR}*")
+}
+
+#[cfg(test)]
+mod tests {
+    use redis::{FromRedisValue, ToRedisArgs, Value};
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn to_redis_args_is_canonical_string() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        assert_eq!(id.to_redis_args(), vec![b"game:item/sword".to_vec()]);
+    }
+
+    #[test]
+    fn from_redis_value() {
+        let id = IdentifierUntyped::parse("game:stone").unwrap();
+        let bulk = Value::BulkString(b"game:stone".to_vec());
+        assert_eq!(IdentifierUntyped::from_redis_value_ref(&bulk).unwrap(), id);
+        let simple = Value::SimpleString("game:stone".to_owned());
+        assert_eq!(IdentifierUntyped::from_redis_value(simple).unwrap(), id);
+    }
+
+    #[test]
+    fn from_redis_value_rejects_invalid() {
+        let value = Value::BulkString(b"game:Stone".to_vec());
+        let err = IdentifierUntyped::from_redis_value_ref(&value).unwrap_err();
+        assert!(err.to_string().contains("invalid identifier"));
+        let err = IdentifierUntyped::from_redis_value(value).unwrap_err();
+        assert!(err.to_string().contains("invalid identifier"));
+        assert!(IdentifierUntyped::from_redis_value(Value::Nil).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
+
+use rkyv::{
+    Place, SerializeUnsized,
+    rancor::{Fallible, Source},
+    string::{ArchivedString, StringResolver},
+    with::{ArchiveWith, DeserializeWith, SerializeWith},
+};
+
+use crate::{
+    ArchivedIdentifier, DEFAULT_NAMESPACE, DEFAULT_SEPARATOR, Full, Identifier,
+    IdentifierUntyped, ParseError,
+};
+
+/// Archives the shared full form as a plain [`ArchivedString`].
+#[derive(Debug)]
+pub struct SharedFull;
+
+impl ArchiveWith<Arc<Full>> for SharedFull {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve_with(
+        field: &Arc<Full>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedString::resolve_from_str(&field.string, resolver, out);
+    }
+}
+
+impl<S> SerializeWith<Arc<Full>, S> for SharedFull
+where
+    S: Fallible + ?Sized,
+    S::Error: Source,
+    str: SerializeUnsized<S>,
+{
+    fn serialize_with(
+        field: &Arc<Full>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(&field.string, serializer)
+    }
+}
+
+/// Archives are untrusted input, so the full form is validated like
+/// [`Identifier::new`] on the way back in.
+impl<D> DeserializeWith<ArchivedString, Arc<Full>, D> for SharedFull
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize_with(
+        field: &ArchivedString,
+        _: &mut D,
+    ) -> Result<Arc<Full>, D::Error> {
+        let full = field.as_str();
+        let (namespace, value) = full
+            .split_once(DEFAULT_SEPARATOR)
+            .ok_or(ParseError::MissingNamespace)
+            .map_err(D::Error::new)?;
+        let id =
+            IdentifierUntyped::new(namespace, value).map_err(D::Error::new)?;
+        // `Identifier::new` defaults an empty namespace.
+        if id.as_str() != full {
+            return Err(D::Error::new(ParseError::MissingNamespace));
+        }
+        Ok(id.full)
+    }
+}
+
+impl<T> ArchivedIdentifier<T> {
+    /// Returns the archived namespace as a string slice.
+    pub fn namespace(&self) -> &str {
+        self.parts().0
+    }
+
+    /// Returns the archived value as a string slice.
+    pub fn value(&self) -> &str {
+        self.parts().1
+    }
+
+    fn parts(&self) -> (&str, &str) {
+        self.full
+            .split_once(DEFAULT_SEPARATOR)
+            .unwrap_or(("", &self.full))
+    }
+}
+
+impl<T> Debug for ArchivedIdentifier<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ArchivedIdentifier")
+            .field("namespace", &self.namespace())
+            .field("value", &self.value())
+            .finish()
+    }
+}
+
+impl<T> Display for ArchivedIdentifier<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if f.alternate() && self.namespace() == DEFAULT_NAMESPACE {
+            return f.pad(self.value());
+        }
+        f.pad(&self.full)
+    }
+}
+
+impl<T> PartialEq for ArchivedIdentifier<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.full == other.full
+    }
+}
+
+impl<T> Eq for ArchivedIdentifier<T> {}
+
+impl<T> PartialEq<Identifier<T>> for ArchivedIdentifier<T> {
+    fn eq(&self, other: &Identifier<T>) -> bool {
+        self.namespace() == other.namespace() && self.value() == other.value()
+    }
+}
+
+impl<T> PartialEq<ArchivedIdentifier<T>> for Identifier<T> {
+    fn eq(&self, other: &ArchivedIdentifier<T>) -> bool {
+        other == self
+    }
+}
+
+/// Hashes exactly like [`Identifier`], so archived maps can be queried with
+/// live identifiers through `get_with`.
+impl<T> Hash for ArchivedIdentifier<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        #[cfg(feature = "cached-hash")]
+        state.write_u64(crate::fnv1a(&self.full));
+        #[cfg(all(feature = "fast-hash", not(feature = "cached-hash")))]
+        {
+            state.write_u64(crate::fnv1a(self.namespace()));
+            self.value().hash(state);
+        }
+        #[cfg(not(any(feature = "fast-hash", feature = "cached-hash")))]
+        {
+            self.namespace().hash(state);
+            self.value().hash(state);
+        }
+    }
+}
+
+impl<T> PartialOrd for ArchivedIdentifier<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for ArchivedIdentifier<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.namespace().cmp(other.namespace()) {
+            Ordering::Equal => self.value().cmp(other.value()),
+            non_eq => non_eq,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        hash::{BuildHasher, RandomState},
+    };
+
+    use rkyv::{Archived, rancor::Error};
+
+    use super::ArchivedIdentifier;
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&id).unwrap();
+        let archived =
+            rkyv::access::<ArchivedIdentifier<()>, Error>(&bytes).unwrap();
+        assert_eq!(archived.namespace(), "game");
+        assert_eq!(archived.value(), "item/sword");
+        assert_eq!(*archived, id);
+        assert_eq!(archived.to_string(), id.to_string());
+
+        let back =
+            rkyv::deserialize::<IdentifierUntyped, Error>(archived).unwrap();
+        assert_eq!(back, id);
+    }
+
+    #[test]
+    fn malformed_archives_are_rejected() {
+        for full in ["game", ":stone", "game:Stone", "game:", "Game:stone"] {
+            let bytes = rkyv::to_bytes::<Error>(&full.to_owned()).unwrap();
+            let archived =
+                rkyv::access::<ArchivedIdentifier<()>, Error>(&bytes).unwrap();
+            // Reading the parts never panics, even on a malformed archive.
+            let _ = (archived.namespace(), archived.value());
+            assert!(
+                rkyv::deserialize::<IdentifierUntyped, Error>(archived)
+                    .is_err(),
+                "{full}"
+            );
+        }
+    }
+
+    #[test]
+    fn hash_matches_live_type() {
+        let id = IdentifierUntyped::parse("game:stone").unwrap();
+        let bytes = rkyv::to_bytes::<Error>(&id).unwrap();
+        let archived =
+            rkyv::access::<ArchivedIdentifier<()>, Error>(&bytes).unwrap();
+        let state = RandomState::new();
+        assert_eq!(state.hash_one(archived), state.hash_one(&id));
+    }
+
+    #[test]
+    fn archived_map_lookup_in_place() {
+        let map: HashMap<IdentifierUntyped, u32> = [
+            (IdentifierUntyped::parse("game:stone").unwrap(), 1),
+            (IdentifierUntyped::parse("game:dirt").unwrap(), 2),
+        ]
+        .into_iter()
+        .collect();
+        let bytes = rkyv::to_bytes::<Error>(&map).unwrap();
+        let archived = rkyv::access::<
+            Archived<HashMap<IdentifierUntyped, u32>>,
+            Error,
+        >(&bytes)
+        .unwrap();
+
+        let key = IdentifierUntyped::parse("game:dirt").unwrap();
+        let value = archived.get_with(&key, |live, archived| archived == live);
+        assert_eq!(value.map(|v| v.to_native()), Some(2));
+    }
+}
@@ -0,0 +1,125 @@
+//! Rocket support. Identifiers can be used as dynamic path segments
+//! (`<id>`, or `<id..>` for values containing `/`) and as form fields.
+//!
+//! Take a `Result<Identifier<T>, ParseError>` in a route to respond with a
+//! `400 Bad Request` carrying the parse error message, since [`ParseError`]
+//! is a [`Responder`].
+
+use std::io::Cursor;
+
+use rocket::{
+    Request, Response,
+    form::{self, FromFormField, ValueField},
+    http::{ContentType, Status, uri::Segments, uri::fmt::Path},
+    request::{FromParam, FromSegments},
+    response::{self, Responder},
+};
+
+use crate::{Identifier, ParseError};
+
+impl<'a, T> FromParam<'a> for Identifier<T> {
+    type Error = ParseError;
+
+    fn from_param(param: &'a str) -> Result<Self, Self::Error> {
+        Identifier::parse_str(param)
+    }
+}
+
+impl<'r, T> FromSegments<'r> for Identifier<T> {
+    type Error = ParseError;
+
+    fn from_segments(
+        segments: Segments<'r, Path>,
+    ) -> Result<Self, Self::Error> {
+        Identifier::parse(segments.collect::<Vec<_>>().join("/"))
+    }
+}
+
+impl<'v, T> FromFormField<'v> for Identifier<T> {
+    fn from_value(field: ValueField<'v>) -> form::Result<'v, Self> {
+        Identifier::parse_str(field.value).map_err(|err| {
+            form::Error::from((
+                Status::BadRequest,
+                Box::new(err) as Box<dyn std::error::Error + Send>,
+            ))
+            .into()
+        })
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ParseError {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let body = self.to_string();
+        Response::build()
+            .status(Status::BadRequest)
+            .header(ContentType::Plain)
+            .sized_body(body.len(), Cursor::new(body))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rocket::{
+        FromForm, form::Form, get, http::Status, local::blocking::Client,
+        routes,
+    };
+
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[get("/id/<id>")]
+    fn single(
+        id: Result<IdentifierUntyped, ParseError>,
+    ) -> Result<String, ParseError> {
+        id.map(|id| id.to_string())
+    }
+
+    #[get("/path/<id..>")]
+    fn segments(id: IdentifierUntyped) -> String {
+        id.to_string()
+    }
+
+    fn client() -> Client {
+        let rocket = rocket::build().mount("/", routes![single, segments]);
+        Client::tracked(rocket).unwrap()
+    }
+
+    #[test]
+    fn path_segment() {
+        let client = client();
+        let response = client.get("/id/game:stone").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "game:stone");
+
+        let response = client.get("/path/game:item/sword").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        assert_eq!(response.into_string().unwrap(), "game:item/sword");
+    }
+
+    #[test]
+    fn invalid_path_segment_is_bad_request() {
+        let client = client();
+        let response = client.get("/id/game:Stone").dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+        assert!(
+            response
+                .into_string()
+                .unwrap()
+                .contains("illegal character(s) in value")
+        );
+    }
+
+    #[derive(Debug, FromForm)]
+    struct Query {
+        id: IdentifierUntyped,
+    }
+
+    #[test]
+    fn form_field() {
+        let query = Form::<Query>::parse("id=game:stone").unwrap();
+        assert_eq!(query.id.to_string(), "game:stone");
+
+        let errors = Form::<Query>::parse("id=game:Stone").unwrap_err();
+        assert_eq!(errors.status(), Status::BadRequest);
+    }
+}
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+
+use crate::{IDENTIFIER_DESCRIPTION, IDENTIFIER_PATTERN, Identifier};
+
+impl<T> JsonSchema for Identifier<T> {
+    fn schema_name() -> Cow<'static, str> {
+        "Identifier".into()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        concat!(module_path!(), "::Identifier").into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "pattern": IDENTIFIER_PATTERN,
+            "description": IDENTIFIER_DESCRIPTION,
+            "examples": ["game:item/sword"],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schemars::schema_for;
+
+    use crate::{
+        IDENTIFIER_DESCRIPTION, IDENTIFIER_PATTERN, Identifier,
+        IdentifierUntyped,
+    };
+
+    #[test]
+    fn schema_is_pattern_string() {
+        let schema = schema_for!(IdentifierUntyped);
+        assert_eq!(schema.get("type").and_then(|v| v.as_str()), Some("string"));
+        assert_eq!(
+            schema.get("pattern").and_then(|v| v.as_str()),
+            Some(IDENTIFIER_PATTERN)
+        );
+        assert_eq!(
+            schema.get("description").and_then(|v| v.as_str()),
+            Some(IDENTIFIER_DESCRIPTION)
+        );
+    }
+
+    #[test]
+    fn schema_ignores_marker_type() {
+        struct Block;
+        assert_eq!(
+            schema_for!(Identifier<Block>),
+            schema_for!(IdentifierUntyped)
+        );
+    }
+}
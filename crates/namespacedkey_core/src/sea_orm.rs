@@ -0,0 +1,108 @@
+//! SeaORM support. Identifiers are stored as their `namespace:value` string
+//! in `TEXT` columns.
+
+use sea_orm::{
+    ColIdx, ColumnType, DbErr, QueryResult, TryFromU64, TryGetError,
+    TryGetable, Value,
+    sea_query::{ArrayType, Nullable, ValueType, ValueTypeErr},
+};
+
+use crate::Identifier;
+
+impl<T> From<Identifier<T>> for Value {
+    fn from(id: Identifier<T>) -> Value {
+        Value::String(Some(Box::new(id.as_str().to_owned())))
+    }
+}
+
+impl<T> TryGetable for Identifier<T> {
+    fn try_get_by<I: ColIdx>(
+        res: &QueryResult,
+        index: I,
+    ) -> Result<Self, TryGetError> {
+        let s = String::try_get_by(res, index)?;
+        Identifier::parse(s).map_err(|err| {
+            TryGetError::DbErr(DbErr::TryIntoErr {
+                from: "String",
+                into: "Identifier",
+                source: Box::new(err),
+            })
+        })
+    }
+}
+
+impl<T> ValueType for Identifier<T> {
+    fn try_from(v: Value) -> Result<Self, ValueTypeErr> {
+        match v {
+            Value::String(Some(s)) => {
+                Identifier::parse(*s).map_err(|_| ValueTypeErr)
+            }
+            _ => Err(ValueTypeErr),
+        }
+    }
+
+    fn type_name() -> String {
+        "Identifier".to_owned()
+    }
+
+    fn array_type() -> ArrayType {
+        ArrayType::String
+    }
+
+    fn column_type() -> ColumnType {
+        ColumnType::Text
+    }
+}
+
+impl<T> Nullable for Identifier<T> {
+    fn null() -> Value {
+        Value::String(None)
+    }
+}
+
+/// Identifiers can be used as primary keys, but never auto-increment.
+impl<T> TryFromU64 for Identifier<T> {
+    fn try_from_u64(_: u64) -> Result<Self, DbErr> {
+        Err(DbErr::ConvertFromU64("Identifier"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{
+        ColumnType, Value,
+        sea_query::{Nullable, ValueType},
+    };
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn value_round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let value = Value::from(id.clone());
+        assert_eq!(
+            value,
+            Value::String(Some(Box::new("game:item/sword".to_owned())))
+        );
+        assert_eq!(
+            <IdentifierUntyped as ValueType>::try_from(value).unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn value_rejects_invalid() {
+        let value = Value::String(Some(Box::new("game:Stone".to_owned())));
+        assert!(<IdentifierUntyped as ValueType>::try_from(value).is_err());
+        assert!(
+            <IdentifierUntyped as ValueType>::try_from(Value::Int(None))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn column_metadata() {
+        assert_eq!(IdentifierUntyped::column_type(), ColumnType::Text);
+        assert_eq!(IdentifierUntyped::null(), Value::String(None));
+    }
+}
@@ -0,0 +1,390 @@
+//! Alternative [serde] representations for [`Identifier`].
+//!
+//! By default an [`Identifier`] is (de)serialized as its `namespace:value`
+//! string in human-readable formats (JSON, YAML, TOML, ...) and as a compact
+//! `(namespace, value)` tuple in binary formats (bincode, postcard, ...), as
+//! reported by [`Serializer::is_human_readable`]. The modules in here can be
+//! used with `#[serde(with = "...")]` to pick a different representation for
+//! a single field.
+//!
+//! [serde]: https://docs.rs/serde/latest/serde/
+//! [`Identifier`]: crate::Identifier
+
+use std::{
+    fmt::{Formatter, Result as FmtResult},
+    marker::PhantomData,
+};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{Error, SeqAccess, Unexpected, Visitor},
+};
+
+use crate::Identifier;
+
+impl<T> Serialize for Identifier<T> {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (self.namespace(), self.value()).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Identifier<T> {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let visitor = IdentifierVisitor(PhantomData);
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(visitor)
+        } else {
+            deserializer.deserialize_tuple(2, visitor)
+        }
+    }
+}
+
+/// Parses straight from whatever string the deserializer hands over, so
+/// borrowed input is never copied into an intermediate `String`.
+struct IdentifierVisitor<T>(PhantomData<T>);
+
+impl<'de, T> Visitor<'de> for IdentifierVisitor<T> {
+    type Value = Identifier<T>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(
+            "a `namespace:value` string or a `(namespace, value)` tuple",
+        )
+    }
+
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        Identifier::parse_str(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let s = std::str::from_utf8(v)
+            .map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(s)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> Result<Self::Value, A::Error> {
+        let namespace: String = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let value: String = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        Identifier::new(namespace, value).map_err(A::Error::custom)
+    }
+}
+
+/// (De)serializes an [`Identifier`] as a struct with `namespace` and `value`
+/// fields instead of a `namespace:value` string.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::IdentifierUntyped;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Entry {
+///     #[serde(with = "namespacedkey_core::serde::as_struct")]
+///     id: IdentifierUntyped,
+/// }
+/// ```
+///
+/// The `id` field above is written as
+/// `{ "namespace": "game", "value": "item/sword" }`.
+///
+/// [`Identifier`]: crate::Identifier
+pub mod as_struct {
+    use serde::{
+        Deserialize, Deserializer, Serialize, Serializer, de::Error as _,
+    };
+
+    use crate::Identifier;
+
+    #[derive(Serialize)]
+    #[serde(rename = "Identifier")]
+    struct Borrowed<'a> {
+        namespace: &'a str,
+        value: &'a str,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "Identifier")]
+    struct Owned {
+        namespace: String,
+        value: String,
+    }
+
+    /// Serializes `id` as a two-field struct.
+    pub fn serialize<T, S: Serializer>(
+        id: &Identifier<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        Borrowed {
+            namespace: id.namespace(),
+            value: id.value(),
+        }
+        .serialize(serializer)
+    }
+
+    /// Deserializes a two-field struct into an [`Identifier`], validating
+    /// both parts.
+    pub fn deserialize<'de, T, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Identifier<T>, D::Error> {
+        let Owned { namespace, value } = Owned::deserialize(deserializer)?;
+        Identifier::new(namespace, value).map_err(D::Error::custom)
+    }
+}
+
+/// (De)serializes an [`Identifier`] as a string that leaves out the
+/// namespace when it is [`DEFAULT_NAMESPACE`], for files written by hand.
+/// Both forms are accepted when deserializing.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::IdentifierUntyped;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Entry {
+///     #[serde(with = "namespacedkey_core::serde::compact")]
+///     id: IdentifierUntyped,
+/// }
+/// ```
+///
+/// The `id` field above is written as `"stone"` for `unspecified:stone`,
+/// and as `"game:stone"` otherwise. For another default namespace, call
+/// [`serialize_in`](compact::serialize_in) and
+/// [`deserialize_in`](compact::deserialize_in) from your own
+/// `serialize_with` and `deserialize_with` functions.
+///
+/// [`Identifier`]: crate::Identifier
+/// [`DEFAULT_NAMESPACE`]: crate::DEFAULT_NAMESPACE
+pub mod compact {
+    use std::{
+        fmt::{Formatter, Result as FmtResult},
+        marker::PhantomData,
+    };
+
+    use serde::{
+        Deserializer, Serializer,
+        de::{Error, Visitor},
+    };
+
+    use crate::{DEFAULT_NAMESPACE, Identifier};
+
+    /// Serializes `id`, without its namespace if it is
+    /// [`DEFAULT_NAMESPACE`].
+    pub fn serialize<T, S: Serializer>(
+        id: &Identifier<T>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_in(id, DEFAULT_NAMESPACE, serializer)
+    }
+
+    /// Deserializes a string with or without a namespace, defaulting it to
+    /// [`DEFAULT_NAMESPACE`].
+    pub fn deserialize<'de, T, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Identifier<T>, D::Error> {
+        deserialize_in(deserializer, DEFAULT_NAMESPACE)
+    }
+
+    /// Serializes `id`, without its namespace if it is
+    /// `default_namespace`.
+    pub fn serialize_in<T, S: Serializer>(
+        id: &Identifier<T>,
+        default_namespace: &str,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if id.namespace() == default_namespace {
+            serializer.serialize_str(id.value())
+        } else {
+            serializer.serialize_str(id.as_str())
+        }
+    }
+
+    /// Deserializes a string with or without a namespace, defaulting it to
+    /// `default_namespace`.
+    pub fn deserialize_in<'de, T, D: Deserializer<'de>>(
+        deserializer: D,
+        default_namespace: &str,
+    ) -> Result<Identifier<T>, D::Error> {
+        deserializer.deserialize_str(CompactVisitor {
+            default_namespace,
+            type_marker: PhantomData,
+        })
+    }
+
+    struct CompactVisitor<'a, T> {
+        default_namespace: &'a str,
+        type_marker: PhantomData<fn() -> T>,
+    }
+
+    impl<'de, T> Visitor<'de> for CompactVisitor<'_, T> {
+        type Value = Identifier<T>;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+            f.write_str("a `namespace:value` or `value` string")
+        }
+
+        fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+            Identifier::parse_with_default(v, self.default_namespace)
+                .map_err(E::custom)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn string_round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#""game:item/sword""#);
+        assert_eq!(
+            serde_json::from_str::<IdentifierUntyped>(&json).unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn string_from_owned_input() {
+        // Escapes force serde_json to hand the visitor an owned string.
+        let json = r#""game:item\u002fsword""#;
+        let id: IdentifierUntyped = serde_json::from_str(json).unwrap();
+        assert_eq!(id.value(), "item/sword");
+
+        let reader = std::io::Cursor::new(r#""game:stone""#);
+        let id: IdentifierUntyped = serde_json::from_reader(reader).unwrap();
+        assert_eq!(id.to_string(), "game:stone");
+    }
+
+    #[test]
+    fn string_rejects_illegal_chars() {
+        let err = serde_json::from_str::<IdentifierUntyped>(r#""game:Stone""#)
+            .unwrap_err();
+        assert!(err.to_string().contains("illegal character(s) in value"));
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Entry {
+        #[serde(with = "super::as_struct")]
+        id: IdentifierUntyped,
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let bytes = postcard::to_allocvec(&id).unwrap();
+        // Two length-prefixed strings, no separator.
+        assert_eq!(bytes.len(), 1 + "game".len() + 1 + "item/sword".len());
+        assert_eq!(
+            postcard::from_bytes::<IdentifierUntyped>(&bytes).unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn binary_rejects_illegal_chars() {
+        let bytes = postcard::to_allocvec(&("game", "Sword")).unwrap();
+        assert!(postcard::from_bytes::<IdentifierUntyped>(&bytes).is_err());
+    }
+
+    #[test]
+    fn as_struct_round_trip() {
+        let entry = Entry {
+            id: IdentifierUntyped::parse("game:item/sword").unwrap(),
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, r#"{"id":{"namespace":"game","value":"item/sword"}}"#);
+        assert_eq!(serde_json::from_str::<Entry>(&json).unwrap(), entry);
+    }
+
+    #[test]
+    fn as_struct_rejects_illegal_value() {
+        let json = r#"{"id":{"namespace":"game","value":"Sword"}}"#;
+        assert!(serde_json::from_str::<Entry>(json).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Compact {
+        #[serde(with = "super::compact")]
+        id: IdentifierUntyped,
+    }
+
+    #[test]
+    fn compact_omits_default_namespace() {
+        let entry = Compact {
+            id: IdentifierUntyped::parse("stone").unwrap(),
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, r#"{"id":"stone"}"#);
+        assert_eq!(serde_json::from_str::<Compact>(&json).unwrap(), entry);
+
+        let json = r#"{"id":"unspecified:stone"}"#;
+        assert_eq!(serde_json::from_str::<Compact>(json).unwrap(), entry);
+    }
+
+    #[test]
+    fn compact_keeps_other_namespaces() {
+        let entry = Compact {
+            id: IdentifierUntyped::parse("game:stone").unwrap(),
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, r#"{"id":"game:stone"}"#);
+        assert_eq!(serde_json::from_str::<Compact>(&json).unwrap(), entry);
+    }
+
+    fn in_game<S: Serializer>(
+        id: &IdentifierUntyped,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::compact::serialize_in(id, "game", serializer)
+    }
+
+    fn from_game<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<IdentifierUntyped, D::Error> {
+        super::compact::deserialize_in(deserializer, "game")
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct CompactInGame {
+        #[serde(serialize_with = "in_game", deserialize_with = "from_game")]
+        id: IdentifierUntyped,
+    }
+
+    #[test]
+    fn compact_in_configured_namespace() {
+        let entry = CompactInGame {
+            id: IdentifierUntyped::parse("game:stone").unwrap(),
+        };
+        let json = serde_json::to_string(&entry).unwrap();
+        assert_eq!(json, r#"{"id":"stone"}"#);
+        assert_eq!(
+            serde_json::from_str::<CompactInGame>(&json).unwrap(),
+            entry
+        );
+        assert!(
+            serde_json::from_str::<CompactInGame>(r#"{"id":"St"}"#).is_err()
+        );
+    }
+}
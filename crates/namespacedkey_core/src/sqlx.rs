@@ -0,0 +1,75 @@
+//! sqlx support. Identifiers are stored as their `namespace:value` string in
+//! any column type the database accepts for Rust strings (`TEXT`, `VARCHAR`,
+//! ...).
+
+use sqlx::{
+    Database, Decode, Encode, Type, encode::IsNull, error::BoxDynError,
+};
+
+use crate::Identifier;
+
+impl<T, DB: Database> Type<DB> for Identifier<T>
+where
+    str: Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <str as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <str as Type<DB>>::compatible(ty)
+    }
+}
+
+/// Encodes the shared string in place. Backends whose `&str` encoder only
+/// accepts a borrow for the whole query, like SQLite, are not covered.
+impl<'q, T, DB: Database> Encode<'q, DB> for Identifier<T>
+where
+    for<'a> &'a str: Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut DB::ArgumentBuffer<'q>,
+    ) -> Result<IsNull, BoxDynError> {
+        self.as_str().encode(buf)
+    }
+}
+
+/// Decoding validates the stored string; malformed values surface as decode
+/// errors carrying the [`ParseError`](crate::ParseError).
+impl<'r, T, DB: Database> Decode<'r, DB> for Identifier<T>
+where
+    &'r str: Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+        let s = <&str as Decode<DB>>::decode(value)?;
+        Ok(Identifier::parse_str(s)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{
+        Encode, Postgres, Type, TypeInfo,
+        postgres::{PgArgumentBuffer, PgTypeInfo},
+    };
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn type_matches_text() {
+        let ty = <IdentifierUntyped as Type<Postgres>>::type_info();
+        assert_eq!(ty.name(), "TEXT");
+        assert!(<IdentifierUntyped as Type<Postgres>>::compatible(
+            &PgTypeInfo::with_name("VARCHAR")
+        ));
+    }
+
+    #[test]
+    fn encodes_canonical_string() {
+        let id = IdentifierUntyped::parse("game:stone").unwrap();
+        let mut buf = PgArgumentBuffer::default();
+        let _ = Encode::<Postgres>::encode_by_ref(&id, &mut buf).unwrap();
+        assert_eq!(&buf[..], b"game:stone");
+    }
+}
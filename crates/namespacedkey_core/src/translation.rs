@@ -0,0 +1,158 @@
+//! Mapping between identifiers and dotted translation keys such as
+//! `item.game.iron_sword`, as used by localization files.
+
+use crate::{Identifier, ParseError};
+
+/// Error returned when a translation key cannot be turned back into an
+/// [`Identifier`].
+#[derive(Debug, thiserror::Error)]
+pub enum TranslationKeyError {
+    /// The key does not start with the expected prefix.
+    #[error("translation key {0:?} does not start with {1:?}")]
+    MissingPrefix(String, String),
+
+    /// The key has no `.` between the namespace and the value.
+    #[error("translation key {0:?} has no namespace")]
+    MissingNamespace(String),
+
+    /// The namespace and value do not form a valid identifier.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+impl<T> Identifier<T> {
+    /// Returns the translation key for this identifier, as
+    /// `<prefix>.<namespace>.<value>` with each `/` in the value replaced by
+    /// a `.`. An empty `prefix` is left out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:tools/iron_sword").unwrap();
+    /// assert_eq!(id.to_translation_key("item"), "item.game.tools.iron_sword");
+    /// ```
+    pub fn to_translation_key(&self, prefix: &str) -> String {
+        let mut key = String::with_capacity(
+            prefix.len() + self.namespace().len() + self.value().len() + 2,
+        );
+        if !prefix.is_empty() {
+            key.push_str(prefix);
+            key.push('.');
+        }
+        key.push_str(self.namespace());
+        key.push('.');
+        key.extend(self.value().chars().map(|ch| match ch {
+            '/' => '.',
+            ch => ch,
+        }));
+        key
+    }
+
+    /// Parses a translation key produced by
+    /// [`to_translation_key`](Self::to_translation_key) with the same
+    /// `prefix`.
+    ///
+    /// The mapping is lossy: the namespace is taken up to the first `.`
+    /// after the prefix, and every later `.` becomes a `/` in the value. Keys
+    /// of identifiers with a `.` in their namespace or value therefore do
+    /// not map back to the original.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::from_translation_key(
+    ///     "item",
+    ///     "item.game.tools.iron_sword",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(id.to_string(), "game:tools/iron_sword");
+    /// ```
+    pub fn from_translation_key(
+        prefix: &str,
+        key: &str,
+    ) -> Result<Self, TranslationKeyError> {
+        let rest = if prefix.is_empty() {
+            Some(key)
+        } else {
+            key.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('.'))
+        };
+        let rest = rest.ok_or_else(|| {
+            TranslationKeyError::MissingPrefix(
+                key.to_owned(),
+                prefix.to_owned(),
+            )
+        })?;
+        let (namespace, value) = rest.split_once('.').ok_or_else(|| {
+            TranslationKeyError::MissingNamespace(key.to_owned())
+        })?;
+        Ok(Identifier::new(
+            namespace.to_owned(),
+            value.replace('.', "/"),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TranslationKeyError;
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[test]
+    fn round_trip() {
+        for input in ["game:iron_sword", "game:tools/iron_sword"] {
+            let id = IdentifierUntyped::parse(input).unwrap();
+            let key = id.to_translation_key("item");
+            assert_eq!(
+                IdentifierUntyped::from_translation_key("item", &key).unwrap(),
+                id
+            );
+        }
+    }
+
+    #[test]
+    fn empty_prefix() {
+        let id = IdentifierUntyped::parse("game:stone").unwrap();
+        assert_eq!(id.to_translation_key(""), "game.stone");
+        assert_eq!(
+            IdentifierUntyped::from_translation_key("", "game.stone").unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn dots_are_lossy() {
+        let id = IdentifierUntyped::parse("game:sword.v2").unwrap();
+        let key = id.to_translation_key("item");
+        assert_eq!(key, "item.game.sword.v2");
+        let back =
+            IdentifierUntyped::from_translation_key("item", &key).unwrap();
+        assert_eq!(back.value(), "sword/v2");
+    }
+
+    #[test]
+    fn from_translation_key_errors() {
+        assert!(matches!(
+            IdentifierUntyped::from_translation_key("item", "block.game.stone"),
+            Err(TranslationKeyError::MissingPrefix(..))
+        ));
+        assert!(matches!(
+            IdentifierUntyped::from_translation_key("item", "itemgame.stone"),
+            Err(TranslationKeyError::MissingPrefix(..))
+        ));
+        assert!(matches!(
+            IdentifierUntyped::from_translation_key("item", "item.game"),
+            Err(TranslationKeyError::MissingNamespace(_))
+        ));
+        assert!(matches!(
+            IdentifierUntyped::from_translation_key("item", "item.game.Stone"),
+            Err(TranslationKeyError::Parse(ParseError::IllegalCharsInValue(
+                ..
+            )))
+        ));
+    }
+}
@@ -0,0 +1,118 @@
+//! Marker types that constrain the identifiers they mark.
+
+use crate::{Identifier, ParseError};
+
+/// Describes the identifiers marked with `Self`, e.g. every block
+/// identifier in a game.
+///
+/// Implementing it is optional: [`Identifier::parse`] accepts any key for
+/// any marker, and only the methods here enforce [`NAMESPACE`].
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{Identifier, typed::IdentifierType};
+///
+/// struct Block;
+///
+/// impl IdentifierType for Block {
+///     const NAMESPACE: Option<&'static str> = Some("game");
+///     const CATEGORY: &'static str = "block";
+/// }
+///
+/// let stone = Identifier::<Block>::parse_typed("stone").unwrap();
+/// assert_eq!(stone, "game:stone");
+/// let err = Identifier::<Block>::parse_typed("mod:stone").unwrap_err();
+/// assert_eq!(
+///     err.to_string(),
+///     r#"expected a block in namespace "game", found namespace "mod""#
+/// );
+/// ```
+///
+/// [`NAMESPACE`]: IdentifierType::NAMESPACE
+pub trait IdentifierType {
+    /// The namespace every identifier of this type is in, if there is one.
+    const NAMESPACE: Option<&'static str> = None;
+
+    /// What identifiers of this type name, like `block` or `sound`, for
+    /// error messages.
+    const CATEGORY: &'static str;
+}
+
+impl<T: IdentifierType> Identifier<T> {
+    /// Parses a string like [`Identifier::parse`], but defaults a missing
+    /// namespace to [`T::NAMESPACE`](IdentifierType::NAMESPACE) and fails
+    /// with [`ParseError::WrongNamespace`] for any other namespace.
+    pub fn parse_typed(s: &str) -> Result<Self, ParseError> {
+        let id = match T::NAMESPACE {
+            Some(namespace) => Self::parse_with_default(s, namespace)?,
+            None => Self::parse_str(s)?,
+        };
+        id.check_type()?;
+        Ok(id)
+    }
+
+    /// Checks that the namespace is
+    /// [`T::NAMESPACE`](IdentifierType::NAMESPACE), if that is set.
+    pub fn check_type(&self) -> Result<(), ParseError> {
+        match T::NAMESPACE {
+            Some(expected) if self.namespace() != expected => {
+                Err(ParseError::WrongNamespace {
+                    category: T::CATEGORY,
+                    expected,
+                    found: self.namespace_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns [`T::CATEGORY`](IdentifierType::CATEGORY).
+    pub fn category(&self) -> &'static str {
+        T::CATEGORY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdentifierType;
+    use crate::{Identifier, ParseError};
+
+    struct Block;
+
+    impl IdentifierType for Block {
+        const NAMESPACE: Option<&'static str> = Some("game");
+        const CATEGORY: &'static str = "block";
+    }
+
+    struct Sound;
+
+    impl IdentifierType for Sound {
+        const CATEGORY: &'static str = "sound";
+    }
+
+    #[test]
+    fn enforces_namespace() {
+        let id = Identifier::<Block>::parse_typed("game:stone").unwrap();
+        assert_eq!(id.category(), "block");
+        assert_eq!(Identifier::<Block>::parse_typed("stone").unwrap(), id);
+
+        let err = Identifier::<Block>::parse_typed("mod:stone").unwrap_err();
+        assert!(matches!(
+            &err,
+            ParseError::WrongNamespace { category: "block", expected: "game", found }
+                if found == "mod"
+        ));
+        assert_eq!(err.underline("mod:stone"), "mod:stone\n^");
+
+        let other = Identifier::<Block>::parse("mod:stone").unwrap();
+        assert!(other.check_type().is_err());
+    }
+
+    #[test]
+    fn any_namespace_without_one_set() {
+        let id = Identifier::<Sound>::parse_typed("mod:beep").unwrap();
+        assert!(id.check_type().is_ok());
+        assert!(Identifier::<Sound>::parse_typed("mod:Beep").is_err());
+    }
+}
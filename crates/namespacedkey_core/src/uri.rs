@@ -0,0 +1,114 @@
+//! Percent-encoding for carrying identifiers in URL path segments and query
+//! strings.
+
+use std::fmt::Write;
+
+use crate::{DEFAULT_SEPARATOR, Identifier, ParseError};
+
+impl<T> Identifier<T> {
+    /// Returns this identifier with every `/` in its value percent-encoded,
+    /// so it fits in a single URL path segment or query parameter.
+    ///
+    /// All other legal characters are unreserved in URLs and are left as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+    /// assert_eq!(id.to_uri_component(), "game:item%2Fsword");
+    /// ```
+    pub fn to_uri_component(&self) -> String {
+        let mut out = String::with_capacity(
+            self.namespace().len() + 1 + self.value().len() * 3,
+        );
+        out.push_str(self.namespace());
+        out.push(DEFAULT_SEPARATOR);
+        for ch in self.value().chars() {
+            if ch.is_ascii_alphanumeric() || "-._~".contains(ch) {
+                out.push(ch);
+            } else {
+                let mut buf = [0; 4];
+                for byte in ch.encode_utf8(&mut buf).bytes() {
+                    // Writing to a `String` cannot fail.
+                    let _ = write!(out, "%{byte:02X}");
+                }
+            }
+        }
+        out
+    }
+
+    /// Percent-decodes `s` and parses the result like [`Identifier::parse`].
+    ///
+    /// Escapes are case-insensitive. A `%` that does not start an escape of
+    /// an ASCII character is kept literally and then rejected as illegal, so
+    /// decoding never lets through anything [`Identifier::parse`] would not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::from_uri_component("game%3Aitem%2fsword")
+    ///     .unwrap();
+    /// assert_eq!(id.to_string(), "game:item/sword");
+    /// ```
+    pub fn from_uri_component(s: &str) -> Result<Self, ParseError> {
+        let mut decoded = String::with_capacity(s.len());
+        let mut rest = s;
+        while let Some(idx) = rest.find('%') {
+            decoded.push_str(&rest[..idx]);
+            rest = &rest[idx..];
+            let byte = rest
+                .get(1..3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+            match byte {
+                Some(byte) if byte.is_ascii() => {
+                    decoded.push(char::from(byte));
+                    rest = &rest[3..];
+                }
+                _ => {
+                    decoded.push('%');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        decoded.push_str(rest);
+        Identifier::parse_str(&decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[test]
+    fn round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword/v1.2").unwrap();
+        let encoded = id.to_uri_component();
+        assert_eq!(encoded, "game:item%2Fsword%2Fv1.2");
+        assert_eq!(
+            IdentifierUntyped::from_uri_component(&encoded).unwrap(),
+            id
+        );
+    }
+
+    #[test]
+    fn unencoded_input_is_accepted() {
+        let id =
+            IdentifierUntyped::from_uri_component("game:item/sword").unwrap();
+        assert_eq!(id.value(), "item/sword");
+    }
+
+    #[test]
+    fn decoding_preserves_validation() {
+        for input in ["game:%41", "game:a%2", "game:a%zz", "game:%C3%A9"] {
+            let err = IdentifierUntyped::from_uri_component(input).unwrap_err();
+            assert!(
+                matches!(err, ParseError::IllegalCharsInValue(..)),
+                "{input}"
+            );
+        }
+    }
+}
@@ -0,0 +1,208 @@
+//! Conversions between [`Identifier`] and [`Url`], for asset-URI style
+//! addressing such as `res://game/item/sword`.
+//!
+//! The namespace becomes the URL's host and the value its path. Special
+//! schemes like `http` normalize their hosts, so only other schemes are
+//! used, and characters a custom charset allows but URLs don't are
+//! percent-encoded.
+//!
+//! [`Identifier`]: crate::Identifier
+
+use percent_encoding::{
+    AsciiSet, NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode,
+};
+use url::Url;
+
+use crate::{Identifier, ParseError};
+
+/// Everything but unreserved characters is encoded in the host.
+const HOST: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// The path keeps its `/` separators as well.
+const PATH: &AsciiSet = &HOST.remove(b'/');
+
+/// Schemes whose hosts URLs lowercase, IDNA-encode or read as IP addresses.
+const SPECIAL_SCHEMES: [&str; 6] =
+    ["ftp", "file", "http", "https", "ws", "wss"];
+
+/// Error returned when an [`Identifier`] cannot be converted to or from a
+/// [`Url`].
+#[derive(Debug, thiserror::Error)]
+pub enum UrlError {
+    /// The resulting string is not a valid URL, e.g. because of the scheme.
+    #[error(transparent)]
+    Url(#[from] url::ParseError),
+
+    /// The scheme is one whose hosts URLs normalize, like `http`.
+    #[error("scheme {0:?} normalizes hosts, so namespaces would not survive")]
+    SpecialScheme(String),
+
+    /// The URL's host or path is not valid percent-encoded UTF-8.
+    #[error("URL host or path is not valid UTF-8 once decoded")]
+    InvalidUtf8,
+
+    /// The value has a `.` or `..` path segment, which URLs normalize away.
+    #[error("value {0:?} contains a `.` or `..` path segment")]
+    DotSegment(String),
+
+    /// The URL has no host to use as the namespace.
+    #[error("URL has no host")]
+    MissingHost,
+
+    /// The host and path do not form a valid identifier.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+impl<T> Identifier<T> {
+    /// Returns this identifier as a `scheme://namespace/value` URL.
+    ///
+    /// Fails if `scheme` is not a valid URL scheme or is a special one like
+    /// `http`, or if the value contains `.` or `..` segments. Either would
+    /// not survive URL normalization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+    /// assert_eq!(id.to_url("res").unwrap().as_str(), "res://game/item/sword");
+    /// ```
+    pub fn to_url(&self, scheme: &str) -> Result<Url, UrlError> {
+        if SPECIAL_SCHEMES
+            .iter()
+            .any(|special| scheme.eq_ignore_ascii_case(special))
+        {
+            return Err(UrlError::SpecialScheme(scheme.to_owned()));
+        }
+        if self.value().split('/').any(|seg| seg == "." || seg == "..") {
+            return Err(UrlError::DotSegment(self.value().to_owned()));
+        }
+        Ok(Url::parse(&format!(
+            "{scheme}://{}/{}",
+            utf8_percent_encode(self.namespace(), HOST),
+            utf8_percent_encode(self.value(), PATH),
+        ))?)
+    }
+
+    /// Builds an identifier from a URL's host and path, the reverse of
+    /// [`to_url`](Self::to_url). Both are percent-decoded; the scheme,
+    /// query and fragment are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    /// use url::Url;
+    ///
+    /// let url = Url::parse("res://game/item/sword").unwrap();
+    /// let id = IdentifierUntyped::from_url(&url).unwrap();
+    /// assert_eq!(id.to_string(), "game:item/sword");
+    /// ```
+    pub fn from_url(url: &Url) -> Result<Self, UrlError> {
+        let decode = |s| {
+            percent_decode_str(s)
+                .decode_utf8()
+                .map_err(|_| UrlError::InvalidUtf8)
+        };
+        let namespace = decode(url.host_str().ok_or(UrlError::MissingHost)?)?;
+        let value = decode(url.path().strip_prefix('/').unwrap_or(url.path()))?;
+        Ok(Identifier::new(namespace.into_owned(), value.into_owned())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use url::Url;
+
+    use super::UrlError;
+    use crate::{IdentifierUntyped, ParseError};
+
+    #[test]
+    fn round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword.png").unwrap();
+        let url = id.to_url("res").unwrap();
+        assert_eq!(url.host_str(), Some("game"));
+        assert_eq!(url.path(), "/item/sword.png");
+        assert_eq!(IdentifierUntyped::from_url(&url).unwrap(), id);
+    }
+
+    proptest! {
+        #[test]
+        fn every_identifier_round_trips(
+            namespace in "[0-9a-z_.-]{1,16}",
+            value in "[0-9a-z_./-]{1,32}",
+        ) {
+            let id = IdentifierUntyped::new(namespace, value).unwrap();
+            match id.to_url("res") {
+                Ok(url) => {
+                    prop_assert_eq!(IdentifierUntyped::from_url(&url).unwrap(), id);
+                }
+                Err(err) => prop_assert!(matches!(err, UrlError::DotSegment(_))),
+            }
+        }
+    }
+
+    #[test]
+    fn host_like_namespaces_round_trip() {
+        for input in
+            ["0x1:a", "1.2.3.4:a", "game.:a", "xn--a:a", "a..b:a", "..:a"]
+        {
+            let id = IdentifierUntyped::parse(input).unwrap();
+            let url = id.to_url("res").unwrap();
+            assert_eq!(IdentifierUntyped::from_url(&url).unwrap(), id);
+        }
+    }
+
+    #[test]
+    fn to_url_rejects_special_schemes() {
+        let id = IdentifierUntyped::parse("game.:stone").unwrap();
+        for scheme in ["http", "HTTPS", "file"] {
+            assert!(matches!(
+                id.to_url(scheme),
+                Err(UrlError::SpecialScheme(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn to_url_rejects_dot_segments() {
+        let id = IdentifierUntyped::parse("game:item/../sword").unwrap();
+        assert!(matches!(id.to_url("res"), Err(UrlError::DotSegment(_))));
+        let id = IdentifierUntyped::parse("game:item/.sword").unwrap();
+        assert!(id.to_url("res").is_ok());
+    }
+
+    #[test]
+    fn to_url_rejects_bad_scheme() {
+        let id = IdentifierUntyped::parse("game:stone").unwrap();
+        assert!(matches!(id.to_url("1res"), Err(UrlError::Url(_))));
+    }
+
+    #[test]
+    fn from_url_validates() {
+        let url = Url::parse("res://game/Sword").unwrap();
+        assert!(matches!(
+            IdentifierUntyped::from_url(&url),
+            Err(UrlError::Parse(ParseError::IllegalCharsInValue(..)))
+        ));
+
+        let url = Url::parse("res:/item/sword").unwrap();
+        assert!(matches!(
+            IdentifierUntyped::from_url(&url),
+            Err(UrlError::MissingHost)
+        ));
+
+        let url = Url::parse("res://game/").unwrap();
+        assert!(matches!(
+            IdentifierUntyped::from_url(&url),
+            Err(UrlError::Parse(ParseError::EmptyValue))
+        ));
+    }
+}
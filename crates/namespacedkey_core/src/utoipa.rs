@@ -0,0 +1,49 @@
+use std::borrow::Cow;
+
+use utoipa::{
+    PartialSchema, ToSchema,
+    openapi::{ObjectBuilder, RefOr, Schema, Type},
+};
+
+use crate::{IDENTIFIER_DESCRIPTION, IDENTIFIER_PATTERN, Identifier};
+
+impl<T> PartialSchema for Identifier<T> {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::String)
+            .pattern(Some(IDENTIFIER_PATTERN))
+            .description(Some(IDENTIFIER_DESCRIPTION))
+            .examples(["game:item/sword"])
+            .into()
+    }
+}
+
+impl<T> ToSchema for Identifier<T> {
+    fn name() -> Cow<'static, str> {
+        "Identifier".into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use utoipa::{
+        PartialSchema, ToSchema,
+        openapi::{RefOr, Schema, Type},
+    };
+
+    use crate::{
+        IDENTIFIER_DESCRIPTION, IDENTIFIER_PATTERN, IdentifierUntyped,
+    };
+
+    #[test]
+    fn schema_is_pattern_string() {
+        let RefOr::T(Schema::Object(object)) = IdentifierUntyped::schema()
+        else {
+            panic!("expected an inline object schema");
+        };
+        assert!(object.schema_type == Type::String.into());
+        assert_eq!(object.pattern.as_deref(), Some(IDENTIFIER_PATTERN));
+        assert_eq!(object.description.as_deref(), Some(IDENTIFIER_DESCRIPTION));
+        assert_eq!(IdentifierUntyped::name(), "Identifier");
+    }
+}
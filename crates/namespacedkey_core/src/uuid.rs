@@ -0,0 +1,71 @@
+//! Deterministic [UUID v5] derivation, for handing identifiers to external
+//! systems that key on UUIDs.
+//!
+//! [UUID v5]: https://www.rfc-editor.org/rfc/rfc9562#section-5.5
+
+use uuid::Uuid;
+
+use crate::Identifier;
+
+impl<T> Identifier<T> {
+    /// Derives a UUID v5 from the canonical `namespace:value` form of this
+    /// identifier within `namespace_uuid`.
+    ///
+    /// # Stability
+    ///
+    /// The result is the SHA-1 based UUID v5 of exactly the bytes produced by
+    /// this identifier's [`Display`](std::fmt::Display) impl, so it can be
+    /// reproduced by any RFC 9562 implementation and will not change between
+    /// releases of this crate. An identifier parsed without a namespace hashes
+    /// as [`DEFAULT_NAMESPACE`](crate::DEFAULT_NAMESPACE)`:value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    /// use uuid::Uuid;
+    ///
+    /// let id = IdentifierUntyped::parse("game:stone").unwrap();
+    /// assert_eq!(
+    ///     id.to_uuid_v5(Uuid::NAMESPACE_OID).to_string(),
+    ///     "77d1b682-7e53-5cf7-b2ec-547ce3b0739f"
+    /// );
+    /// ```
+    pub fn to_uuid_v5(&self, namespace_uuid: Uuid) -> Uuid {
+        Uuid::new_v5(&namespace_uuid, self.as_str().as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::IdentifierUntyped;
+
+    #[test]
+    fn matches_canonical_string() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        assert_eq!(
+            id.to_uuid_v5(Uuid::NAMESPACE_URL),
+            Uuid::new_v5(&Uuid::NAMESPACE_URL, b"game:item/sword")
+        );
+    }
+
+    #[test]
+    fn default_namespace_is_stable() {
+        let id = IdentifierUntyped::parse("stone").unwrap();
+        assert_eq!(
+            id.to_uuid_v5(Uuid::NAMESPACE_OID).to_string(),
+            "bec84fee-befb-5262-88c2-4c6d09493756"
+        );
+    }
+
+    #[test]
+    fn depends_on_namespace_uuid() {
+        let id = IdentifierUntyped::parse("game:stone").unwrap();
+        assert_ne!(
+            id.to_uuid_v5(Uuid::NAMESPACE_OID),
+            id.to_uuid_v5(Uuid::NAMESPACE_URL)
+        );
+    }
+}
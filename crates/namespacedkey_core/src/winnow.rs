@@ -0,0 +1,125 @@
+//! A [winnow] parser for embedding identifiers in larger grammars.
+//!
+//! [winnow]: https://docs.rs/winnow/latest/winnow/
+
+use winnow::error::{FromExternalError, ParserError};
+
+use crate::{
+    DEFAULT_SEPARATOR, Identifier, ParseError, namespace_table, value_table,
+};
+
+/// Parses the identifier at the start of `input`, leaving the rest.
+///
+/// The identifier ends at the first character that cannot be part of it,
+/// such as whitespace. Fails without consuming anything if there is no
+/// identifier, or with the [`ParseError`] if what is there is invalid, e.g.
+/// `game:` without a value.
+///
+/// # Examples
+///
+/// ```
+/// use namespacedkey_core::{IdentifierUntyped, winnow::parse_identifier};
+/// use winnow::{Parser, ascii::dec_uint, error::ContextError};
+///
+/// let mut input = "game:stone 64";
+/// let (id, _, count): (IdentifierUntyped, _, u32) =
+///     (parse_identifier, ' ', dec_uint)
+///         .parse_next(&mut input)
+///         .map_err(|_: ContextError| ())
+///         .unwrap();
+/// assert_eq!(id.to_string(), "game:stone");
+/// assert_eq!(count, 64);
+/// assert_eq!(input, "");
+/// ```
+pub fn parse_identifier<'i, T, E>(
+    input: &mut &'i str,
+) -> Result<Identifier<T>, E>
+where
+    E: ParserError<&'i str> + FromExternalError<&'i str, ParseError>,
+{
+    let s: &'i str = input;
+    let mut in_namespace = true;
+    let end = s
+        .char_indices()
+        .find(|&(_, ch)| {
+            if in_namespace && ch == DEFAULT_SEPARATOR {
+                in_namespace = false;
+                return false;
+            }
+            in_namespace &= namespace_table().contains(ch);
+            !value_table().contains(ch)
+        })
+        .map_or(s.len(), |(idx, _)| idx);
+
+    if end == 0 {
+        return Err(E::from_input(input));
+    }
+    match Identifier::parse_str(&s[..end]) {
+        Ok(id) => {
+            *input = &s[end..];
+            Ok(id)
+        }
+        Err(err) => Err(E::from_external_error(input, err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winnow::{
+        Parser,
+        combinator::separated,
+        error::{ContextError, ErrMode},
+    };
+
+    use super::parse_identifier;
+    use crate::IdentifierUntyped;
+
+    type Result<O> = winnow::Result<O, ErrMode<ContextError>>;
+
+    fn parse(input: &mut &str) -> Result<IdentifierUntyped> {
+        parse_identifier(input)
+    }
+
+    #[test]
+    fn consumes_prefix() {
+        let mut input = "game:item/sword rest";
+        assert_eq!(parse(&mut input).unwrap().value(), "item/sword");
+        assert_eq!(input, " rest");
+
+        let mut input = "stone]";
+        assert_eq!(parse(&mut input).unwrap().to_string(), "unspecified:stone");
+        assert_eq!(input, "]");
+    }
+
+    #[test]
+    fn second_separator_ends_identifier() {
+        let mut input = "a:b:c";
+        assert_eq!(parse(&mut input).unwrap().to_string(), "a:b");
+        assert_eq!(input, ":c");
+
+        // `/` can't be in a namespace, so this `:` ends the value.
+        let mut input = "a/b:c";
+        assert_eq!(parse(&mut input).unwrap().value(), "a/b");
+        assert_eq!(input, ":c");
+    }
+
+    #[test]
+    fn errors_do_not_consume() {
+        for original in ["", " game:stone", "Game:stone", "game: stone"] {
+            let mut input = original;
+            assert!(parse(&mut input).is_err(), "{original:?}");
+            assert_eq!(input, original);
+        }
+    }
+
+    #[test]
+    fn composes() {
+        let mut input = "a:b,c:d,e";
+        let ids: Vec<IdentifierUntyped> =
+            separated(1.., parse_identifier::<(), ErrMode<ContextError>>, ',')
+                .parse_next(&mut input)
+                .unwrap();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(input, "");
+    }
+}
@@ -0,0 +1,235 @@
+//! Compact binary encoding for embedding identifiers in custom network
+//! protocols without pulling in serde.
+//!
+//! An identifier is written as its namespace followed by its value, each as
+//! a length-prefixed UTF-8 string, mirroring the `(namespace, value)` tuple
+//! used by the serde impls for binary formats. Lengths are unsigned LEB128
+//! varints and may not exceed [`MAX_PART_LEN`] bytes.
+//!
+//! ```text
+//! varint(ns.len) ns-bytes varint(value.len) value-bytes
+//! ```
+//!
+//! [`Identifier::decode_from`] validates both parts exactly like
+//! [`Identifier::new`].
+//!
+//! [`Identifier::new`]: crate::Identifier::new
+
+use std::{
+    io::{self, Read, Write},
+    string::FromUtf8Error,
+};
+
+use crate::{Identifier, ParseError};
+
+/// The maximum length, in bytes, of an encoded namespace or value.
+pub const MAX_PART_LEN: usize = 32767;
+
+/// Error returned when an [`Identifier`] cannot be encoded or decoded.
+#[derive(Debug, thiserror::Error)]
+pub enum WireError {
+    /// The underlying reader or writer failed.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// A namespace or value is longer than [`MAX_PART_LEN`] bytes.
+    #[error("length {0} exceeds the maximum of {MAX_PART_LEN} bytes")]
+    TooLong(usize),
+
+    /// A length prefix does not fit in 32 bits.
+    #[error("malformed varint length prefix")]
+    MalformedVarInt,
+
+    /// A namespace or value is not valid UTF-8.
+    #[error(transparent)]
+    InvalidUtf8(#[from] FromUtf8Error),
+
+    /// The decoded parts do not form a valid identifier.
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+}
+
+impl<T> Identifier<T> {
+    /// Writes this identifier to `writer` in the [wire format](self).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use namespacedkey_core::IdentifierUntyped;
+    ///
+    /// let id = IdentifierUntyped::parse("game:stone").unwrap();
+    /// let mut buf = Vec::new();
+    /// id.encode_to(&mut buf).unwrap();
+    /// assert_eq!(buf, b"\x04game\x05stone");
+    /// assert_eq!(IdentifierUntyped::decode_from(&mut &buf[..]).unwrap(), id);
+    /// ```
+    pub fn encode_to<W: Write + ?Sized>(
+        &self,
+        writer: &mut W,
+    ) -> Result<(), WireError> {
+        write_part(writer, self.namespace())?;
+        write_part(writer, self.value())
+    }
+
+    /// Reads an identifier in the [wire format](self) from `reader`.
+    ///
+    /// Length prefixes are checked against [`MAX_PART_LEN`] before anything
+    /// is allocated, so untrusted input cannot request huge buffers.
+    pub fn decode_from<R: Read + ?Sized>(
+        reader: &mut R,
+    ) -> Result<Self, WireError> {
+        let namespace = read_part(reader)?;
+        let value = read_part(reader)?;
+        Ok(Identifier::new(namespace, value)?)
+    }
+
+    /// Returns the number of bytes [`encode_to`](Self::encode_to) writes.
+    pub fn encoded_len(&self) -> usize {
+        let part = |len: usize| varint_len(len) + len;
+        part(self.namespace().len()) + part(self.value().len())
+    }
+}
+
+fn write_part<W: Write + ?Sized>(
+    writer: &mut W,
+    part: &str,
+) -> Result<(), WireError> {
+    if part.len() > MAX_PART_LEN {
+        return Err(WireError::TooLong(part.len()));
+    }
+
+    // `part.len()` fits in a u32 after the check above.
+    write_varint(writer, part.len() as u32)?;
+    writer.write_all(part.as_bytes())?;
+    Ok(())
+}
+
+fn read_part<R: Read + ?Sized>(reader: &mut R) -> Result<String, WireError> {
+    let len = read_varint(reader)?.ok_or(WireError::MalformedVarInt)? as usize;
+    if len > MAX_PART_LEN {
+        return Err(WireError::TooLong(len));
+    }
+    let mut buf = vec![0; len];
+    reader.read_exact(&mut buf)?;
+    Ok(String::from_utf8(buf)?)
+}
+
+/// Writes `value` as an unsigned LEB128 varint.
+pub(crate) fn write_varint<W: Write + ?Sized>(
+    writer: &mut W,
+    mut value: u32,
+) -> io::Result<()> {
+    let mut buf = [0; 5];
+    let mut n = 0;
+    loop {
+        // Truncation is intended: only the low seven bits are kept.
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[n] = byte;
+            n += 1;
+            break;
+        }
+        buf[n] = byte | 0x80;
+        n += 1;
+    }
+    writer.write_all(&buf[..n])
+}
+
+/// Reads an unsigned LEB128 varint, returning `None` if it does not fit in
+/// a u32.
+pub(crate) fn read_varint<R: Read + ?Sized>(
+    reader: &mut R,
+) -> io::Result<Option<u32>> {
+    let mut value: u32 = 0;
+    for shift in (0..35).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        let bits = u32::from(byte[0] & 0x7f);
+        if shift == 28 && bits > 0x0f {
+            return Ok(None);
+        }
+        value |= bits << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Ok(None)
+}
+
+fn varint_len(len: usize) -> usize {
+    let bits = usize::BITS - len.leading_zeros();
+    (bits.max(1) as usize).div_ceil(7)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_PART_LEN, WireError};
+    use crate::{IdentifierUntyped, ParseError};
+
+    fn encode(id: &IdentifierUntyped) -> Vec<u8> {
+        let mut buf = Vec::new();
+        id.encode_to(&mut buf).unwrap();
+        buf
+    }
+
+    #[test]
+    fn round_trip() {
+        let id = IdentifierUntyped::parse("game:item/sword").unwrap();
+        let buf = encode(&id);
+        assert_eq!(buf.len(), id.encoded_len());
+        let mut reader = &buf[..];
+        assert_eq!(IdentifierUntyped::decode_from(&mut reader).unwrap(), id);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn multi_byte_length() {
+        let value = "a".repeat(300);
+        let id = IdentifierUntyped::new("game", &value).unwrap();
+        let buf = encode(&id);
+        assert_eq!(&buf[5..7], &[0xac, 0x02]);
+        assert_eq!(buf.len(), id.encoded_len());
+        assert_eq!(IdentifierUntyped::decode_from(&mut &buf[..]).unwrap(), id);
+    }
+
+    #[test]
+    fn encode_rejects_long_parts() {
+        let value = "a".repeat(MAX_PART_LEN + 1);
+        let id = IdentifierUntyped::new("game", &value).unwrap();
+        let err = id.encode_to(&mut Vec::new()).unwrap_err();
+        assert!(
+            matches!(err, WireError::TooLong(len) if len == MAX_PART_LEN + 1)
+        );
+    }
+
+    #[test]
+    fn decode_rejects_long_prefix_before_reading() {
+        let buf = [0xff, 0xff, 0x03];
+        let err = IdentifierUntyped::decode_from(&mut &buf[..]).unwrap_err();
+        assert!(matches!(err, WireError::TooLong(_)));
+
+        let buf = [0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        let err = IdentifierUntyped::decode_from(&mut &buf[..]).unwrap_err();
+        assert!(matches!(err, WireError::MalformedVarInt));
+    }
+
+    #[test]
+    fn decode_validates() {
+        let err =
+            IdentifierUntyped::decode_from(&mut &b"\x04game\x05Stone"[..])
+                .unwrap_err();
+        assert!(matches!(
+            err,
+            WireError::Parse(ParseError::IllegalCharsInValue(..))
+        ));
+
+        let err = IdentifierUntyped::decode_from(&mut &b"\x04game\x01\xff"[..])
+            .unwrap_err();
+        assert!(matches!(err, WireError::InvalidUtf8(_)));
+
+        let err = IdentifierUntyped::decode_from(&mut &b"\x04game\x05sto"[..])
+            .unwrap_err();
+        assert!(matches!(err, WireError::Io(_)));
+    }
+}
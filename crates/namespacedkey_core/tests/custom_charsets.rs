@@ -0,0 +1,21 @@
+//! Custom charsets are process-wide and can only be installed before first
+//! use, so they get their own test binary.
+
+use namespacedkey_core::{
+    IdentifierUntyped, legal_namespace_chars, set_legal_namespace_chars,
+    set_legal_value_chars,
+};
+
+#[test]
+fn custom_charsets() {
+    set_legal_value_chars("abc+".chars().collect()).unwrap();
+    set_legal_namespace_chars("xyz".chars().collect()).unwrap();
+
+    let id = IdentifierUntyped::parse("xyz:a+b").unwrap();
+    assert_eq!(id.value(), "a+b");
+    assert!(IdentifierUntyped::parse("xyz:d").is_err());
+    assert!(IdentifierUntyped::parse("game:abc").is_err());
+
+    let chars = legal_namespace_chars().clone();
+    assert!(set_legal_namespace_chars(chars).is_err());
+}
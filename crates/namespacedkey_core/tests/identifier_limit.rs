@@ -0,0 +1,76 @@
+//! A limit on the whole identifier that the default namespace alone nearly
+//! fills, in its own test binary since limits are process-wide.
+#![cfg(any(
+    feature = "arbitrary",
+    feature = "proptest",
+    feature = "quickcheck"
+))]
+
+use namespacedkey_core::{
+    DEFAULT_NAMESPACE, IdentifierUntyped,
+    limits::{LengthLimits, set_length_limits},
+};
+
+const LIMITS: LengthLimits = LengthLimits {
+    identifier: Some(DEFAULT_NAMESPACE.len() + 1),
+    ..LengthLimits::UNLIMITED
+};
+
+/// Installs [`LIMITS`], whichever test runs first.
+fn install() {
+    if let Err(limits) = set_length_limits(LIMITS) {
+        assert_eq!(limits, LIMITS);
+    }
+}
+
+fn assert_fits(id: &IdentifierUntyped) {
+    assert_ne!(id.namespace(), DEFAULT_NAMESPACE);
+    assert!(id.as_str().len() <= DEFAULT_NAMESPACE.len() + 1);
+    assert_eq!(
+        IdentifierUntyped::parse(id.as_str()).ok().as_ref(),
+        Some(id)
+    );
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck_fits_identifier_limit() {
+    use quickcheck::{Arbitrary, Gen};
+
+    install();
+    let mut g = Gen::new(64);
+    for _ in 0..100 {
+        assert_fits(&IdentifierUntyped::arbitrary(&mut g));
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_fits_identifier_limit() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    install();
+    let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+    let mut u = Unstructured::new(&data);
+    while let Ok(id) = IdentifierUntyped::arbitrary(&mut u) {
+        assert_fits(&id);
+        if u.is_empty() {
+            break;
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn proptest_fits_identifier_limit() {
+    use namespacedkey_core::proptest::any_identifier;
+    use proptest::test_runner::TestRunner;
+
+    install();
+    TestRunner::default()
+        .run(&any_identifier::<()>(), |id| {
+            assert_fits(&id);
+            Ok(())
+        })
+        .unwrap();
+}
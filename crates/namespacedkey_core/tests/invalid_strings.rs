@@ -0,0 +1,27 @@
+//! `invalid_identifier_string` under custom charsets, in its own test
+//! binary since charsets are process-wide.
+#![cfg(feature = "proptest")]
+
+use namespacedkey_core::{
+    IdentifierUntyped, legal_value_chars, proptest::invalid_identifier_string,
+    set_legal_value_chars,
+};
+use proptest::test_runner::TestRunner;
+
+#[test]
+fn invalid_strings_follow_custom_charsets() {
+    // Uppercase and punctuation are legal in values here.
+    let chars = ('a'..='z')
+        .chain('A'..='Z')
+        .chain(" !@#$%^&*/".chars())
+        .collect();
+    set_legal_value_chars(chars).unwrap();
+    assert!(legal_value_chars().contains(&'A'));
+
+    TestRunner::default()
+        .run(&invalid_identifier_string(), |s| {
+            assert!(IdentifierUntyped::parse(&s).is_err(), "{s:?}");
+            Ok(())
+        })
+        .unwrap();
+}
@@ -0,0 +1,85 @@
+//! Length limits are process-wide and can only be installed once, so they
+//! get their own test binary.
+
+use namespacedkey_core::{
+    IdentifierUntyped, ParseError,
+    limits::{LengthLimits, LengthOf, set_length_limits},
+};
+
+const LIMITS: LengthLimits = LengthLimits {
+    value: Some(4),
+    ..LengthLimits::UNLIMITED
+};
+
+/// Installs [`LIMITS`], whichever test runs first.
+fn install() {
+    if let Err(limits) = set_length_limits(LIMITS) {
+        assert_eq!(limits, LIMITS);
+    }
+}
+
+#[test]
+fn parse_lossy_checks_repaired_lengths() {
+    install();
+
+    // Six bytes as written, but three once each `ß` becomes `_`.
+    let (id, changed) = IdentifierUntyped::parse_lossy("game:ßßß").unwrap();
+    assert_eq!(id, "game:___");
+    assert!(changed);
+
+    assert!(matches!(
+        IdentifierUntyped::parse_lossy("game:abcde"),
+        Err(ParseError::TooLong {
+            of: LengthOf::Value,
+            limit: 4,
+            len: 5
+        })
+    ));
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck_respects_limits() {
+    use quickcheck::{Arbitrary, Gen};
+
+    install();
+    let mut g = Gen::new(64);
+    for _ in 0..100 {
+        let id = IdentifierUntyped::arbitrary(&mut g);
+        assert!(id.value().len() <= 4);
+        assert_eq!(IdentifierUntyped::parse(id.as_str()).unwrap(), id);
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_respects_limits() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    install();
+    let data: Vec<u8> = (0..=255).cycle().take(4096).collect();
+    let mut u = Unstructured::new(&data);
+    while let Ok(id) = IdentifierUntyped::arbitrary(&mut u) {
+        assert!(id.value().len() <= 4);
+        assert_eq!(IdentifierUntyped::parse(id.as_str()).unwrap(), id);
+        if u.is_empty() {
+            break;
+        }
+    }
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn proptest_respects_limits() {
+    use namespacedkey_core::proptest::any_identifier;
+    use proptest::test_runner::TestRunner;
+
+    install();
+    TestRunner::default()
+        .run(&any_identifier::<()>(), |id| {
+            assert!(id.value().len() <= 4);
+            assert_eq!(IdentifierUntyped::parse(id.as_str()).unwrap(), id);
+            Ok(())
+        })
+        .unwrap();
+}
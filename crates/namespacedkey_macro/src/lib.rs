@@ -1,41 +1,135 @@
-use namespacedkey_core::Identifier;
+use std::collections::HashMap;
+
+use namespacedkey_core::{
+    DEFAULT_SEPARATOR, Identifier, phf, reader::IdentifierReader,
+};
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
-    Ident, LitStr, Token, Type, Visibility,
+    Attribute, DeriveInput, Expr, Ident, LitStr, Token, Type, Visibility,
+    ext::IdentExt,
     parse::{Parse, ParseStream},
     parse_macro_input,
+    punctuated::Punctuated,
     token::Comma,
 };
 
 struct Entry {
+    /// Outer attributes, forwarded onto the generated function.
+    attrs: Vec<Attribute>,
     vis: Visibility,
     ident: Ident,
-    value: LitStr,
+    /// The literal as written, for error spans.
+    lit: LitStr,
+    /// The parts of the validated literal, split at compile time.
+    namespace: String,
+    value: String,
 }
 
-/// Macro input: an optional `T` followed by one or more `Entry` definitions.
+/// Macro input: optional `type T;`, `namespace "...";`, `[vis] all_ids;`
+/// and `[vis] mod name;` headers in any order, then one or more `Entry`
+/// definitions. A leading `T;` also sets the marker type when `T` is not a
+/// plain name, like `crate::Block;` or `();`.
 struct MacroInput {
     ty: Option<Type>,
+    /// The visibility of `all_ids` and `COUNT`, if they were asked for.
+    all_ids: Option<Visibility>,
+    /// The module to group the entries in by namespace, if asked for.
+    module: Option<(Visibility, Ident)>,
     entries: Vec<Entry>,
 }
 
 impl Parse for MacroInput {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let ty = {
+        // A leading `T;` sets the marker type only if `T` can't be read as
+        // an entry; a bare name is ambiguous and needs `type T;`.
+        let mut ty = None;
+        let fork = input.fork();
+        if let Ok(parsed) = fork.parse::<Type>()
+            && fork.peek(Token![;])
+        {
+            match bare_ident(&parsed) {
+                None => {
+                    input.parse::<Type>()?;
+                    input.parse::<Token![;]>()?;
+                    ty = Some(parsed);
+                }
+                Some(ident) if looks_like_type(&ident.to_string()) => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        format!(
+                            "ambiguous `{ident};`: write `type {ident};` for \
+                             a marker type, or `{ident} => \"...\"` for an \
+                             entry"
+                        ),
+                    ));
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut namespace = None;
+        let mut all_ids = None;
+        let mut module = None;
+        loop {
+            if input.peek(Ident)
+                && input.peek2(LitStr)
+                && input.fork().parse::<Ident>()? == "namespace"
+            {
+                input.parse::<Ident>()?;
+                let lit: LitStr = input.parse()?;
+                input.parse::<Token![;]>()?;
+                if let Err(err) =
+                    Identifier::<()>::new(lit.value(), "value".to_owned())
+                {
+                    return Err(syn::Error::new_spanned(
+                        &lit,
+                        format!("Invalid namespace: {err}"),
+                    ));
+                }
+                namespace = Some(lit.value());
+                continue;
+            }
+
+            if input.peek(Token![type]) {
+                let keyword = input.parse::<Token![type]>()?;
+                if ty.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        keyword,
+                        "the marker type is already set",
+                    ));
+                }
+                ty = Some(input.parse()?);
+                input.parse::<Token![;]>()?;
+                continue;
+            }
+
             let fork = input.fork();
-            if fork.parse::<Type>().is_ok() && fork.peek(Token![;]) {
-                // Consume it from the real input
-                let ty: Type = input.parse()?;
+            if fork.parse::<Visibility>().is_ok()
+                && fork.parse::<Ident>().is_ok_and(|ident| ident == "all_ids")
+                && fork.peek(Token![;])
+            {
+                all_ids = Some(input.parse::<Visibility>()?);
+                input.parse::<Ident>()?;
                 input.parse::<Token![;]>()?;
-                Some(ty)
-            } else {
-                None
+                continue;
+            }
+
+            let fork = input.fork();
+            if fork.parse::<Visibility>().is_ok() && fork.peek(Token![mod]) {
+                let vis = input.parse::<Visibility>()?;
+                input.parse::<Token![mod]>()?;
+                module = Some((vis, input.parse::<Ident>()?));
+                input.parse::<Token![;]>()?;
+                continue;
             }
-        };
+
+            break;
+        }
 
         let mut entries = Vec::new();
         while !input.is_empty() {
+            let attrs = input.call(Attribute::parse_outer)?;
             let vis: Visibility = if input.peek(Token![pub]) {
                 input.parse()?
             } else {
@@ -43,52 +137,802 @@ impl Parse for MacroInput {
             };
 
             let ident: Ident = input.parse()?;
-            input.parse::<Token![=>]>()?;
-            let value: LitStr = input.parse()?;
+            // Without `=> "..."` the value is the name in snake case
+            let lit = if input.peek(Token![=>]) {
+                input.parse::<Token![=>]>()?;
+                input.parse()?
+            } else {
+                LitStr::new(
+                    &snake_case(&ident.unraw().to_string()),
+                    ident.span(),
+                )
+            };
 
-            // Validate the literal at compile time
-            if let Err(err) = Identifier::<()>::parse(value.value()) {
-                return Err(syn::Error::new_spanned(
-                    &value,
-                    format!("Invalid Identifier: {err}"),
-                ));
+            // Values without a separator inherit the block's namespace
+            let mut full = lit.value();
+            if let Some(namespace) = &namespace
+                && !full.contains(DEFAULT_SEPARATOR)
+            {
+                full = format!("{namespace}{DEFAULT_SEPARATOR}{full}");
             }
 
-            entries.push(Entry { vis, ident, value });
+            // Validate the literal at compile time
+            let id = match Identifier::<()>::parse(full) {
+                Ok(id) => id,
+                Err(err) => {
+                    return Err(syn::Error::new_spanned(
+                        &lit,
+                        format!("Invalid Identifier: {err}"),
+                    ));
+                }
+            };
+            let (namespace, value) = id.into_parts();
+
+            entries.push(Entry {
+                attrs,
+                vis,
+                ident,
+                lit,
+                namespace,
+                value,
+            });
 
-            // Consume an optional trailing comma
+            // Consume an optional trailing comma or semicolon
             if input.peek(Comma) {
                 input.parse::<Comma>()?;
+            } else if input.peek(Token![;]) {
+                input.parse::<Token![;]>()?;
             } else {
                 break;
             }
         }
 
-        Ok(MacroInput { ty, entries })
+        check_duplicates(&entries)?;
+
+        Ok(MacroInput {
+            ty,
+            all_ids,
+            module,
+            entries,
+        })
     }
 }
 
+/// Returns the name if `ty` is a plain identifier, which could also be an
+/// entry.
+fn bare_ident(ty: &Type) -> Option<&Ident> {
+    match ty {
+        Type::Path(path) if path.qself.is_none() => path.path.get_ident(),
+        _ => None,
+    }
+}
+
+/// Returns `true` for `UpperCamelCase` names, which are almost certainly
+/// types rather than entries.
+fn looks_like_type(name: &str) -> bool {
+    name.starts_with(|ch: char| ch.is_ascii_uppercase())
+        && name.contains(|ch: char| ch.is_ascii_lowercase())
+}
+
+/// Fails on a repeated name or identifier, pointing at both entries.
+fn check_duplicates(entries: &[Entry]) -> syn::Result<()> {
+    let mut names = HashMap::new();
+    let mut ids = HashMap::new();
+    for entry in entries {
+        let (first, span, message) = if let Some(first) =
+            names.insert(entry.ident.to_string(), entry.ident.span())
+        {
+            (first, entry.ident.span(), "duplicate identifier name")
+        } else if let Some(first) = ids.insert(
+            (entry.namespace.as_str(), entry.value.as_str()),
+            entry.lit.span(),
+        ) {
+            (first, entry.lit.span(), "duplicate identifier")
+        } else {
+            continue;
+        };
+        let mut err = syn::Error::new(span, message);
+        err.combine(syn::Error::new(first, "first defined here"));
+        return Err(err);
+    }
+    Ok(())
+}
+
 #[proc_macro]
 pub fn define_identifier(input: TokenStream) -> TokenStream {
-    let MacroInput { ty, entries } = parse_macro_input!(input as MacroInput);
+    let MacroInput {
+        ty,
+        all_ids,
+        module,
+        entries,
+    } = parse_macro_input!(input as MacroInput);
 
     // Default to `()` if no type provided.
     let ty = ty.unwrap_or_else(|| syn::parse_quote! { () });
 
-    let fns = entries.into_iter().map(|Entry { vis, ident, value }| {
-        let fn_name = format_ident!("id_{}", ident);
+    let Some((mod_vis, mod_name)) = module else {
+        let names: Vec<_> = entries
+            .iter()
+            .map(|entry| format_ident!("id_{}", entry.ident))
+            .collect();
+        let paths: Vec<_> = names.iter().map(|name| quote!(#name)).collect();
+        let all_ids = all_ids
+            .map(|vis| all_ids_fn(&vis, "all_ids", &ty, &entries, &paths));
+        let fns = entries
+            .iter()
+            .zip(&names)
+            .map(|(entry, name)| entry_fn(entry, &entry.vis, name, &ty));
+        return TokenStream::from(quote! {
+            #( #fns )*
+            #all_ids
+        });
+    };
+
+    // Group by namespace, in order of first appearance.
+    let mut groups: Vec<(&str, Vec<&Entry>)> = Vec::new();
+    for entry in &entries {
+        match groups.iter_mut().find(|(ns, _)| *ns == entry.namespace) {
+            Some((_, group)) => group.push(entry),
+            None => groups.push((&entry.namespace, vec![entry])),
+        }
+    }
+    let public: Visibility = syn::parse_quote!(pub);
+    let mods = groups.iter().map(|(namespace, group)| {
+        let ns_mod = module_ident(namespace);
+        let fns = group
+            .iter()
+            .map(|entry| entry_fn(entry, &public, &entry.ident, &ty));
         quote! {
-            #vis fn #fn_name() -> namespacedkey::Identifier<#ty> {
-                static ONCE: ::std::sync::OnceLock<namespacedkey::Identifier<#ty>> =
-                    ::std::sync::OnceLock::new();
-                ONCE
-                    .get_or_init(|| <namespacedkey::Identifier<#ty> as ::core::str::FromStr>::from_str(#value).unwrap())
-                    .clone()
+            #[doc = concat!("Identifiers in the `", #namespace, "` namespace.")]
+            pub mod #ns_mod {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #( #fns )*
             }
         }
     });
+    let paths: Vec<_> = entries
+        .iter()
+        .map(|entry| {
+            let ns_mod = module_ident(&entry.namespace);
+            let ident = &entry.ident;
+            quote!(#ns_mod::#ident)
+        })
+        .collect();
+    // Inside the module, the module's own visibility is what counts.
+    let all_ids =
+        all_ids.map(|_| all_ids_fn(&public, "all_ids", &ty, &entries, &paths));
+
+    TokenStream::from(quote! {
+        #mod_vis mod #mod_name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #( #mods )*
+            #all_ids
+        }
+    })
+}
+
+/// Generates the function returning one entry's identifier.
+fn entry_fn(
+    entry: &Entry,
+    vis: &Visibility,
+    name: &Ident,
+    ty: &Type,
+) -> proc_macro2::TokenStream {
+    let Entry {
+        attrs,
+        namespace,
+        value,
+        ..
+    } = entry;
+    // The literals were validated and split above, so the generated code
+    // never parses. The `OnceLock` remains because interning the namespace
+    // and allocating the shared string can't happen in a `const`.
+    quote! {
+        #( #attrs )*
+        #vis fn #name() -> namespacedkey::Identifier<#ty> {
+            static ONCE: ::std::sync::OnceLock<namespacedkey::Identifier<#ty>> =
+                ::std::sync::OnceLock::new();
+            ONCE
+                .get_or_init(|| namespacedkey::Identifier::<#ty>::__from_validated_parts(#namespace, #value))
+                .clone()
+        }
+    }
+}
+
+/// Generates `COUNT` and a function called `name` iterating over every
+/// entry, calling the entry functions at `paths`.
+fn all_ids_fn(
+    vis: &Visibility,
+    name: &str,
+    ty: &Type,
+    entries: &[Entry],
+    paths: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    // Entries left out by `#[cfg]` must be left out here too.
+    let cfgs: Vec<Vec<_>> = entries
+        .iter()
+        .map(|entry| {
+            entry
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("cfg"))
+                .collect()
+        })
+        .collect();
+    let name = Ident::new(name, proc_macro2::Span::call_site());
+    quote! {
+        #[doc = concat!("The number of identifiers `", stringify!(#name), "` yields.")]
+        #vis const COUNT: usize = [#( #(#cfgs)* () ),*].len();
+
+        /// Returns every identifier defined alongside this function.
+        #[allow(deprecated)]
+        #vis fn #name() -> impl ::core::iter::Iterator<Item = namespacedkey::Identifier<#ty>> {
+            let ids: [fn() -> namespacedkey::Identifier<#ty>; COUNT] =
+                [#( #(#cfgs)* #paths ),*];
+            ids.into_iter().map(|id| id())
+        }
+    }
+}
+
+/// Turns a namespace into a module name: characters other than ASCII
+/// letters, digits and `_` become `_`, and keywords are made raw.
+fn module_ident(namespace: &str) -> Ident {
+    sanitized_ident(namespace)
+}
+
+/// Replaces characters other than ASCII letters, digits and `_` in `s`
+/// with `_`, making it a valid identifier.
+fn sanitized_ident(s: &str) -> Ident {
+    let mut name: String = s
+        .chars()
+        .map(|ch| if ch.is_ascii_alphanumeric() { ch } else { '_' })
+        .collect();
+    if name.starts_with(|ch: char| ch.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    syn::parse_str::<Ident>(&name).unwrap_or_else(|_| {
+        Ident::new_raw(&name, proc_macro2::Span::call_site())
+    })
+}
+
+/// Expands to an `Identifier` for a literal checked at compile time, e.g.
+/// `registry.get(&id!("game:stone"))`.
+///
+/// An optional marker type comes first: `id!(Block, "game:stone")`. Each
+/// expansion builds its identifier once and clones it after that.
+#[proc_macro]
+pub fn id(input: TokenStream) -> TokenStream {
+    let IdInput { ty, lit } = parse_macro_input!(input as IdInput);
+    let ty = ty.unwrap_or_else(|| syn::parse_quote! { () });
+    let (namespace, value) = match Identifier::<()>::parse(lit.value()) {
+        Ok(id) => id.into_parts(),
+        Err(err) => {
+            return syn::Error::new_spanned(
+                &lit,
+                format!("Invalid Identifier: {err}"),
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+
+    TokenStream::from(quote! {
+        {
+            static ONCE: ::std::sync::OnceLock<namespacedkey::Identifier<#ty>> =
+                ::std::sync::OnceLock::new();
+            ONCE
+                .get_or_init(|| namespacedkey::Identifier::<#ty>::__from_validated_parts(#namespace, #value))
+                .clone()
+        }
+    })
+}
+
+/// `id!` input: an optional `T,` then the literal.
+struct IdInput {
+    ty: Option<Type>,
+    lit: LitStr,
+}
+
+impl Parse for IdInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = if input.peek(LitStr) {
+            None
+        } else {
+            let ty = input.parse()?;
+            input.parse::<Comma>()?;
+            Some(ty)
+        };
+        let lit = input.parse()?;
+        input.parse::<Option<Comma>>()?;
+        Ok(IdInput { ty, lit })
+    }
+}
+
+/// A static perfect-hash set of identifiers, validated at compile time:
+/// `static BLOCKS: IdentifierSet = identifier_phf_set! { "game:stone" };`.
+///
+/// Entries without a namespace get the default one. The build fails on an
+/// invalid or repeated identifier.
+#[proc_macro]
+pub fn identifier_phf_set(input: TokenStream) -> TokenStream {
+    let entries = parse_macro_input!(input with Punctuated::<LitStr, Comma>::parse_terminated);
+    let entries: Vec<_> = entries.into_iter().map(|lit| (lit, None)).collect();
+    match phf_table(entries) {
+        Ok((seed, displacements, keys, _)) => TokenStream::from(quote! {
+            namespacedkey::phf::IdentifierSet::__new(
+                #seed,
+                &[#( #displacements ),*],
+                &[#( (#keys, ()) ),*],
+            )
+        }),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// A static perfect-hash map keyed by identifiers, validated at compile
+/// time: `identifier_phf_map! { "game:stone" => 1.5, "game:dirt" => 0.5 }`.
+///
+/// Keys follow the rules of [`identifier_phf_set!`]; values must be
+/// constant expressions.
+#[proc_macro]
+pub fn identifier_phf_map(input: TokenStream) -> TokenStream {
+    let entries = parse_macro_input!(input with Punctuated::<MapEntry, Comma>::parse_terminated);
+    let entries: Vec<_> = entries
+        .into_iter()
+        .map(|MapEntry { key, value }| (key, Some(value)))
+        .collect();
+    match phf_table(entries) {
+        Ok((seed, displacements, keys, values)) => TokenStream::from(quote! {
+            namespacedkey::phf::IdentifierMap::__new(
+                #seed,
+                &[#( #displacements ),*],
+                &[#( (#keys, #values) ),*],
+            )
+        }),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// `identifier_phf_map!` entry: `"key" => value`.
+struct MapEntry {
+    key: LitStr,
+    value: Expr,
+}
+
+impl Parse for MapEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let value = input.parse()?;
+        Ok(MapEntry { key, value })
+    }
+}
+
+type PhfTable = (
+    u64,
+    Vec<proc_macro2::TokenStream>,
+    Vec<String>,
+    Vec<Option<Expr>>,
+);
+
+/// Validates the keys and lays the entries out in slot order.
+fn phf_table(entries: Vec<(LitStr, Option<Expr>)>) -> syn::Result<PhfTable> {
+    let mut seen: HashMap<String, &LitStr> = HashMap::new();
+    let mut keys = Vec::with_capacity(entries.len());
+    for (lit, _) in &entries {
+        let id = Identifier::<()>::parse(lit.value()).map_err(|err| {
+            syn::Error::new_spanned(lit, format!("Invalid Identifier: {err}"))
+        })?;
+        let key = id.as_str().to_owned();
+        if let Some(first) = seen.get(&key) {
+            let mut err = syn::Error::new_spanned(
+                lit,
+                format!("duplicate identifier `{key}`"),
+            );
+            err.combine(syn::Error::new_spanned(first, "first defined here"));
+            return Err(err);
+        }
+        seen.insert(key.clone(), lit);
+        keys.push(key);
+    }
+
+    let strs: Vec<_> = keys.iter().map(String::as_str).collect();
+    let table = phf::__build(&strs).ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "could not build a perfect hash for these identifiers",
+        )
+    })?;
+    let displacements = table
+        .displacements
+        .iter()
+        .map(|(d1, d2)| quote!((#d1, #d2)))
+        .collect();
+    let mut entries: Vec<_> = entries.into_iter().map(Some).collect();
+    let (keys, values) = table
+        .order
+        .iter()
+        .map(|&idx| {
+            let value = entries[idx].take().and_then(|(_, value)| value);
+            (keys[idx].clone(), value)
+        })
+        .unzip();
+    Ok((table.seed, displacements, keys, values))
+}
+
+/// Generates a function per identifier listed in a file, plus `COUNT` and
+/// an `all()` iterator, e.g. `include_identifiers!(pub Block, "keys.txt")`.
+///
+/// The path is relative to `CARGO_MANIFEST_DIR`. The file is read like
+/// `IdentifierReader` reads it: one identifier per line, with blank lines
+/// and `#` comments skipped. `game:item/sword` becomes `id_game_item_sword`.
+/// The build fails, naming the line, on an invalid or repeated entry.
+#[proc_macro]
+pub fn include_identifiers(input: TokenStream) -> TokenStream {
+    let IncludeInput { vis, ty, path } =
+        parse_macro_input!(input as IncludeInput);
+    let ty = ty.unwrap_or_else(|| syn::parse_quote! { () });
+    let entries = match include_entries(&path, &vis) {
+        Ok(entries) => entries,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let names: Vec<_> = entries
+        .iter()
+        .map(|entry| format_ident!("id_{}", entry.ident))
+        .collect();
+    let paths: Vec<_> = names.iter().map(|name| quote!(#name)).collect();
+    let all = all_ids_fn(&vis, "all", &ty, &entries, &paths);
+    let fns = entries
+        .iter()
+        .zip(&names)
+        .map(|(entry, name)| entry_fn(entry, &entry.vis, name, &ty));
+    let full_path = manifest_path(&path);
 
     TokenStream::from(quote! {
+        // Rebuild when the file changes.
+        const _: &[u8] = include_bytes!(#full_path);
+
         #( #fns )*
+        #all
+    })
+}
+
+/// `include_identifiers!` input: an optional visibility and `T,`, then the
+/// path.
+struct IncludeInput {
+    vis: Visibility,
+    ty: Option<Type>,
+    path: LitStr,
+}
+
+impl Parse for IncludeInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let IdInput { ty, lit } = input.parse()?;
+        Ok(IncludeInput { vis, ty, path: lit })
+    }
+}
+
+fn manifest_path(path: &LitStr) -> String {
+    let dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    std::path::Path::new(&dir)
+        .join(path.value())
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Reads and validates the file at `path`, one entry per identifier.
+fn include_entries(path: &LitStr, vis: &Visibility) -> syn::Result<Vec<Entry>> {
+    let full_path = manifest_path(path);
+    let file = std::fs::read(&full_path).map_err(|err| {
+        syn::Error::new_spanned(
+            path,
+            format!("couldn't read {full_path}: {err}"),
+        )
+    })?;
+
+    let mut entries = Vec::new();
+    let mut lines = HashMap::new();
+    let mut names = HashMap::new();
+    let mut reader = IdentifierReader::<_, ()>::new(file.as_slice());
+    while let Some(id) = reader.next() {
+        let id = id.map_err(|err| {
+            syn::Error::new_spanned(path, format!("{}: {err}", path.value()))
+        })?;
+        let line = reader.line();
+        if let Some(first) = lines.insert(id.as_str().to_owned(), line) {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "{}: line {line}: duplicate identifier `{id}`, first on \
+                     line {first}",
+                    path.value()
+                ),
+            ));
+        }
+        let ident =
+            sanitized_ident(&format!("{}_{}", id.namespace(), id.value()));
+        if let Some(first) = names.insert(ident.to_string(), line) {
+            return Err(syn::Error::new_spanned(
+                path,
+                format!(
+                    "{}: line {line}: duplicate identifier name `{ident}` for \
+                     `{id}`, first on line {first}",
+                    path.value()
+                ),
+            ));
+        }
+        let (namespace, value) = id.into_parts();
+        entries.push(Entry {
+            attrs: Vec::new(),
+            vis: vis.clone(),
+            ident,
+            lit: path.clone(),
+            namespace,
+            value,
+        });
+    }
+    Ok(entries)
+}
+
+/// Implements `IdentifierType` for a marker type.
+///
+/// `#[identifier(namespace = "game", category = "block")]` sets the
+/// namespace and category. Both are optional; the category defaults to the
+/// type name in snake case.
+#[proc_macro_derive(IdentifierType, attributes(identifier))]
+pub fn derive_identifier_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match identifier_type(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn identifier_type(
+    input: &DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut namespace = None;
+    let mut category = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("identifier"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("namespace") {
+                let lit: LitStr = meta.value()?.parse()?;
+                // Validate the namespace at compile time
+                if let Err(err) =
+                    Identifier::<()>::new(lit.value(), "value".to_owned())
+                {
+                    return Err(syn::Error::new_spanned(
+                        &lit,
+                        format!("Invalid namespace: {err}"),
+                    ));
+                }
+                namespace = Some(lit);
+                Ok(())
+            } else if meta.path.is_ident("category") {
+                category = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `namespace` or `category`"))
+            }
+        })?;
+    }
+
+    let ident = &input.ident;
+    let namespace = match namespace {
+        Some(lit) => quote! { ::core::option::Option::Some(#lit) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let category = category
+        .map(|lit| lit.value())
+        .unwrap_or_else(|| snake_case(&ident.to_string()));
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics namespacedkey::typed::IdentifierType
+            for #ident #ty_generics #where_clause
+        {
+            const NAMESPACE: ::core::option::Option<&'static str> = #namespace;
+            const CATEGORY: &'static str = #category;
+        }
+    })
+}
+
+/// Implements `Keyed` with a key checked at compile time, from a
+/// `#[key("game:stone")]` attribute.
+///
+/// Like in `id!`, an optional marker type comes first:
+/// `#[key(Block, "game:stone")]`. The key is built on first use and
+/// borrowed after that.
+#[proc_macro_derive(Keyed, attributes(key))]
+pub fn derive_keyed(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match keyed(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn keyed(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut attrs = input.attrs.iter().filter(|a| a.path().is_ident("key"));
+    let Some(attr) = attrs.next() else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "missing `#[key(\"namespace:value\")]`",
+        ));
+    };
+    if let Some(extra) = attrs.next() {
+        return Err(syn::Error::new_spanned(extra, "duplicate `#[key]`"));
+    }
+    let IdInput { ty, lit } = attr.parse_args()?;
+    let ty = ty.unwrap_or_else(|| syn::parse_quote! { () });
+    let (namespace, value) = Identifier::<()>::parse(lit.value())
+        .map_err(|err| {
+            syn::Error::new_spanned(&lit, format!("Invalid Identifier: {err}"))
+        })?
+        .into_parts();
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics namespacedkey::keyed::Keyed<#ty>
+            for #ident #ty_generics #where_clause
+        {
+            fn key(&self) -> &namespacedkey::Identifier<#ty> {
+                static KEY: ::std::sync::OnceLock<namespacedkey::Identifier<#ty>> =
+                    ::std::sync::OnceLock::new();
+                KEY.get_or_init(|| namespacedkey::Identifier::<#ty>::__from_validated_parts(#namespace, #value))
+            }
+        }
     })
 }
+
+/// `BlockEntity` becomes `block_entity`, `HTTPServer` becomes
+/// `http_server` and `IRON_SWORD` becomes `iron_sword`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (idx, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && idx > 0 {
+            let prev = chars[idx - 1];
+            let next_lower =
+                chars.get(idx + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(ch.to_lowercase());
+    }
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{LitStr, Visibility};
+
+    use super::{MacroInput, include_entries, keyed, snake_case};
+
+    fn error(input: &str) -> String {
+        match syn::parse_str::<MacroInput>(input) {
+            Ok(_) => String::new(),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn rejects_duplicates() {
+        assert_eq!(
+            error(r#"a => "x:a", a => "x:b""#),
+            "duplicate identifier name"
+        );
+        assert_eq!(error(r#"a => "x:a", b => "x:a""#), "duplicate identifier");
+        assert_eq!(
+            error(r#"namespace "x"; a => "a", b => "x:a""#),
+            "duplicate identifier"
+        );
+        assert_eq!(error(r#"a => "x:a", b => "y:a""#), "");
+    }
+
+    #[test]
+    fn keyed_errors() {
+        let error = |input| {
+            let input = syn::parse_str(input).unwrap();
+            keyed(&input)
+                .map(|_| String::new())
+                .unwrap_or_else(|err| err.to_string())
+        };
+        assert_eq!(error(r#"#[key("game:stone")] struct Stone;"#), "");
+        assert_eq!(
+            error("struct Stone;"),
+            r#"missing `#[key("namespace:value")]`"#
+        );
+        assert_eq!(
+            error(r#"#[key("a:b")] #[key("a:c")] struct Stone;"#),
+            "duplicate `#[key]`"
+        );
+        assert!(
+            error(r#"#[key("game:Stone")] struct Stone;"#)
+                .starts_with("Invalid Identifier")
+        );
+    }
+
+    #[test]
+    fn marker_type_headers() {
+        let parse = |input| syn::parse_str::<MacroInput>(input).unwrap();
+        let input = parse("stone; dirt;");
+        assert!(input.ty.is_none());
+        assert_eq!(input.entries.len(), 2);
+        assert!(parse("type Block; stone;").ty.is_some());
+        assert!(parse("crate::Block; stone;").ty.is_some());
+        assert!(parse("(); stone;").ty.is_some());
+
+        assert_eq!(
+            error("Block; stone;"),
+            "ambiguous `Block;`: write `type Block;` for a marker type, or \
+             `Block => \"...\"` for an entry"
+        );
+        assert_eq!(
+            error("type A; type B; stone;"),
+            "the marker type is already set"
+        );
+    }
+
+    #[test]
+    fn snake_case_names() {
+        assert_eq!(snake_case("iron_sword"), "iron_sword");
+        assert_eq!(snake_case("IRON_SWORD"), "iron_sword");
+        assert_eq!(snake_case("BlockEntity"), "block_entity");
+        assert_eq!(snake_case("HTTPServer"), "http_server");
+        assert_eq!(snake_case("Item2Stack"), "item2_stack");
+    }
+
+    fn include_error(contents: &str) -> String {
+        let path = std::env::temp_dir()
+            .join(format!("namespacedkey_include_{}.txt", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        let lit = LitStr::new(
+            &path.to_string_lossy(),
+            proc_macro2::Span::call_site(),
+        );
+        let result = include_entries(&lit, &Visibility::Inherited);
+        std::fs::remove_file(&path).unwrap();
+        match result {
+            Ok(_) => String::new(),
+            Err(err) => err.to_string().replace(&lit.value(), "keys.txt"),
+        }
+    }
+
+    #[test]
+    fn include_reports_lines() {
+        assert_eq!(include_error("# ok\ngame:stone\n"), "");
+        assert_eq!(
+            include_error("game:stone\n\ngame:Dirt\n"),
+            "keys.txt: line 3: illegal character(s) in value \"Dirt\": `D`@0"
+        );
+        assert_eq!(
+            include_error("game:stone\ngame:dirt\ngame:stone\n"),
+            "keys.txt: line 3: duplicate identifier `game:stone`, first on \
+             line 1"
+        );
+        assert_eq!(
+            include_error("game:a.b\ngame:stone\ngame:a_b\n"),
+            "keys.txt: line 3: duplicate identifier name `game_a_b` for \
+             `game:a_b`, first on line 1"
+        );
+    }
+}
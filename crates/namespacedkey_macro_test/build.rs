@@ -0,0 +1,8 @@
+use namespacedkey_codegen::{Codegen, CodegenError};
+
+fn main() -> Result<(), CodegenError> {
+    println!("cargo::rerun-if-changed=tests/keys.txt");
+    let keys = std::fs::read_to_string("tests/keys.txt")?;
+    Codegen::new().csv(&keys)?.write_to_out_dir("keys.rs")?;
+    Ok(())
+}
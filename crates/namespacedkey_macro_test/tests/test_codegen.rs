@@ -0,0 +1,11 @@
+mod keys {
+    include!(concat!(env!("OUT_DIR"), "/keys.rs"));
+}
+
+#[test]
+fn codegen_module_works() {
+    assert_eq!(keys::id_game_item_sword(), "game:item/sword");
+    assert_eq!(keys::COUNT, 3);
+    assert_eq!(keys::ALL, ["game:stone", "game:item/sword", "other:gadget"]);
+    assert_eq!(keys::all().count(), 3);
+}
@@ -10,3 +10,116 @@ fn define_identifier_works() {
 
     assert_eq!(id_foobar(), Identifier::<()>::from_str("foo:bar").unwrap())
 }
+
+#[test]
+fn define_identifier_namespace_block() {
+    define_identifier!(
+        namespace "game";
+        stone => "stone",
+        gadget => "other:gadget",
+    );
+
+    assert_eq!(id_stone(), "game:stone");
+    assert_eq!(id_gadget(), "other:gadget");
+}
+
+#[test]
+fn define_identifier_namespace_block_with_type() {
+    struct Block;
+
+    define_identifier!(
+        type Block;
+        namespace "game";
+        dirt => "dirt"
+    );
+
+    let dirt: Identifier<Block> = id_dirt();
+    assert_eq!(dirt, "game:dirt");
+}
+
+#[test]
+fn define_identifier_all_ids() {
+    define_identifier!(
+        namespace "game";
+        all_ids;
+        stone => "stone",
+        dirt => "dirt",
+    );
+
+    assert_eq!(COUNT, 2);
+    let ids: Vec<_> = all_ids().collect();
+    assert_eq!(ids, [id_stone(), id_dirt()]);
+}
+
+#[test]
+fn define_identifier_forwards_attributes() {
+    define_identifier!(
+        all_ids;
+        /// The stone block.
+        #[must_use]
+        stone => "game:stone",
+        #[deprecated = "use stone"]
+        rock => "game:rock",
+        #[cfg(any())]
+        missing => "game:missing",
+    );
+
+    assert_eq!(id_stone(), "game:stone");
+    #[allow(deprecated)]
+    let rock = id_rock();
+    assert_eq!(rock, "game:rock");
+    assert_eq!(COUNT, 2);
+    assert_eq!(all_ids().count(), 2);
+}
+
+#[test]
+fn define_identifier_leading_bare_entry() {
+    // A type with the same name must not be taken as the marker type
+    #[allow(non_camel_case_types, dead_code)]
+    struct stone;
+
+    define_identifier!(stone; dirt;);
+
+    let id: Identifier<()> = id_stone();
+    assert_eq!(id, "unspecified:stone");
+    assert_eq!(id_dirt(), "unspecified:dirt");
+}
+
+#[test]
+fn define_identifier_value_from_name() {
+    define_identifier!(
+        namespace "game";
+        pub iron_sword;
+        #[allow(non_snake_case)]
+        GOLD_INGOT;
+        r#box => "crate",
+    );
+
+    assert_eq!(id_iron_sword(), "game:iron_sword");
+    assert_eq!(id_GOLD_INGOT(), "game:gold_ingot");
+    assert_eq!(id_box(), "game:crate");
+}
+
+struct Item;
+
+define_identifier!(
+    type Item;
+    pub mod ids;
+    all_ids;
+    namespace "game";
+    stone,
+    gadget => "other:gadget",
+    dirt,
+    sword => "my-mod:sword",
+);
+
+#[test]
+fn define_identifier_grouped_by_namespace() {
+    let stone: Identifier<Item> = ids::game::stone();
+    assert_eq!(stone, "game:stone");
+    assert_eq!(ids::game::dirt(), "game:dirt");
+    assert_eq!(ids::other::gadget(), "other:gadget");
+    assert_eq!(ids::my_mod::sword(), "my-mod:sword");
+    assert_eq!(ids::COUNT, 4);
+    assert_eq!(ids::all_ids().nth(1).unwrap(), "other:gadget");
+}
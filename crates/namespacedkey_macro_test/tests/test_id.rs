@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+
+use namespacedkey::{Identifier, IdentifierUntyped, id};
+
+struct Block;
+
+#[test]
+fn id_works() {
+    let mut registry = HashMap::new();
+    registry.insert(IdentifierUntyped::parse("game:stone").unwrap(), 1);
+    assert_eq!(registry.get(&id!("game:stone")), Some(&1));
+    assert_eq!(id!("stone").namespace(), namespacedkey::DEFAULT_NAMESPACE);
+
+    let block: Identifier<Block> = id!(Block, "game:stone");
+    assert_eq!(block, "game:stone");
+}
@@ -0,0 +1,33 @@
+use std::marker::PhantomData;
+
+use namespacedkey::{Identifier, IdentifierType, typed::IdentifierType as _};
+
+#[derive(IdentifierType)]
+#[identifier(namespace = "game", category = "block")]
+struct Block;
+
+#[derive(IdentifierType)]
+struct BlockEntity;
+
+#[derive(IdentifierType)]
+#[identifier(namespace = "game")]
+struct Tagged<T>(PhantomData<T>);
+
+#[test]
+fn derive_identifier_type_works() {
+    assert_eq!(Block::NAMESPACE, Some("game"));
+    assert_eq!(Block::CATEGORY, "block");
+    assert!(Identifier::<Block>::parse_typed("mod:stone").is_err());
+    assert_eq!(
+        Identifier::<Block>::parse_typed("stone").unwrap(),
+        "game:stone"
+    );
+}
+
+#[test]
+fn derive_identifier_type_defaults() {
+    assert_eq!(BlockEntity::NAMESPACE, None);
+    assert_eq!(BlockEntity::CATEGORY, "block_entity");
+    assert_eq!(<Tagged<u8>>::CATEGORY, "tagged");
+    assert_eq!(<Tagged<u8>>::NAMESPACE, Some("game"));
+}
@@ -0,0 +1,15 @@
+use namespacedkey::{Identifier, include_identifiers};
+
+struct Item;
+
+include_identifiers!(pub Item, "tests/keys.txt");
+
+#[test]
+fn include_identifiers_works() {
+    let sword: Identifier<Item> = id_game_item_sword();
+    assert_eq!(sword, "game:item/sword");
+    assert_eq!(id_other_gadget(), "other:gadget");
+    assert_eq!(COUNT, 3);
+    let all: Vec<_> = all().collect();
+    assert_eq!(all, ["game:stone", "game:item/sword", "other:gadget"]);
+}
@@ -0,0 +1,31 @@
+use std::marker::PhantomData;
+
+use namespacedkey::{Identifier, Keyed, keyed::Keyed as _};
+
+struct Block;
+
+#[derive(Keyed)]
+#[key("game:stone")]
+struct Stone;
+
+#[derive(Keyed)]
+#[key(Block, "dirt")]
+struct Dirt {
+    _hardness: u8,
+}
+
+#[derive(Keyed)]
+#[key("game:chest")]
+struct Chest<T>(PhantomData<T>);
+
+#[test]
+fn derive_keyed() {
+    assert_eq!(Stone.key(), "game:stone");
+    assert!(std::ptr::eq(Stone.key(), Stone.key()));
+
+    let key: &Identifier<Block> = Dirt { _hardness: 1 }.key();
+    assert_eq!(key.namespace(), namespacedkey::DEFAULT_NAMESPACE);
+    assert_eq!(key.value(), "dirt");
+
+    assert_eq!(Chest::<u8>(PhantomData).key(), "game:chest");
+}
@@ -0,0 +1,49 @@
+use namespacedkey::{
+    IdentifierUntyped, id, identifier_phf_map, identifier_phf_set,
+    phf::{IdentifierMap, IdentifierSet},
+};
+
+static SOLID: IdentifierSet = identifier_phf_set! {
+    "game:stone",
+    "game:dirt",
+    "game:planks/oak",
+    "glass",
+};
+
+static HARDNESS: IdentifierMap<f32> = identifier_phf_map! {
+    "game:stone" => 1.5,
+    "game:dirt" => 0.5,
+    "mod:obsidian" => 50.0,
+};
+
+#[test]
+fn set_works() {
+    assert_eq!(SOLID.len(), 4);
+    assert!(SOLID.contains(&id!("game:stone")));
+    assert!(SOLID.contains(&id!("game:planks/oak")));
+    assert!(SOLID.contains_str("unspecified:glass"));
+    assert!(!SOLID.contains(&id!("game:air")));
+    assert!(!SOLID.contains_str("glass"));
+
+    let parsed = IdentifierUntyped::parse("game:dirt").unwrap();
+    assert!(SOLID.contains(&parsed));
+}
+
+#[test]
+fn map_works() {
+    assert_eq!(HARDNESS.get(&id!("game:stone")), Some(&1.5));
+    assert_eq!(HARDNESS.get_str("mod:obsidian"), Some(&50.0));
+    assert_eq!(HARDNESS.get(&id!("game:air")), None);
+    assert!(HARDNESS.contains_key(&id!("game:dirt")));
+
+    let mut keys: Vec<_> = HARDNESS.keys().collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["game:dirt", "game:stone", "mod:obsidian"]);
+}
+
+#[test]
+fn empty_set() {
+    static EMPTY: IdentifierSet = identifier_phf_set! {};
+    assert!(EMPTY.is_empty());
+    assert!(!EMPTY.contains(&id!("game:stone")));
+}
@@ -1,4 +1,7 @@
 pub use namespacedkey_core::*;
 
 #[cfg(feature = "macro")]
-pub use namespacedkey_macro::define_identifier;
+pub use namespacedkey_macro::{
+    IdentifierType, Keyed, define_identifier, id, identifier_phf_map,
+    identifier_phf_set, include_identifiers,
+};